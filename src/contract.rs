@@ -1,26 +1,37 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
-    SubMsgResult,
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdResult, SubMsgResult,
 };
 
 use crate::error::ContractError;
 use crate::execute::{
-    execute_cancel, execute_create, execute_finish, execute_update, execute_update_config,
+    execute_accept_admin, execute_add_native_denom, execute_add_payment_token,
+    execute_block_address, execute_block_name, execute_block_pattern, execute_cancel,
+    execute_create, execute_delist, execute_finish, execute_grant_role, execute_pause,
+    execute_propose_admin, execute_prune_blocked, execute_remove_native_denom,
+    execute_remove_payment_token, execute_renounce_admin, execute_revoke_role,
+    execute_set_collection, execute_set_denom, execute_set_fees, execute_set_treasury,
+    execute_set_update_delay, execute_unblock_address, execute_unblock_name,
+    execute_unblock_pattern, execute_unpause, execute_update,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query::{
-    query_details, query_list, query_swap_total, query_swaps, query_swaps_by_creator,
-    query_swaps_by_denom, query_swaps_by_payment_type, query_swaps_by_price, query_swaps_of_token,
+    query_best_prices, query_blocked_addresses, query_blocked_names, query_blocked_patterns,
+    query_config, query_details, query_details_many, query_has_role, query_last_sale, query_list,
+    query_native_denoms, query_offers_received, query_pause_status, query_payment_tokens,
+    query_pending_admin, query_recent_trades, query_role_members, query_search, query_search_names,
+    query_stats, query_swap_total, query_swaps, query_swaps_by_creator, query_swaps_by_denom,
+    query_swaps_by_payment_type, query_swaps_by_price, query_swaps_of_token, query_trade_history,
+    query_user_activity,
 };
-use crate::state::{Config, SwapType, CONFIG};
+use crate::state::{
+    clear_swap_totals, index_swap, CW721Swap, Config, SwapType, CONFIG, LEGACY_CONFIG, SWAPS,
+};
+use crate::utils::validate_denom;
 
 use cw2::{get_contract_version, set_contract_version};
-
-// Mainnet
-pub static DENOM: &str = "aarch";
-// Testnet
-// pub static DENOM: &str = "aconst";
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:archid-marketplace";
@@ -35,15 +46,22 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    validate_denom(&msg.denom)?;
+
     let config = Config {
-        admin: msg.admin,
-        cw721: msg.cw721.clone(),
+        admin: Some(deps.api.addr_validate(msg.admin.as_str())?),
+        cw721: deps.api.addr_validate(msg.cw721.as_str())?,
+        denom: msg.denom.clone(),
+        fee_bps: 0,
+        treasury: None,
+        update_delay: 0,
     };
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("cw721", msg.cw721))
+        .add_attribute("cw721", msg.cw721)
+        .add_attribute("denom", msg.denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -58,16 +76,67 @@ pub fn execute(
         ExecuteMsg::Finish(msg) => execute_finish(deps, env, info, msg),
         ExecuteMsg::Update(msg) => execute_update(deps, env, info, msg),
         ExecuteMsg::Cancel(msg) => execute_cancel(deps, env, info, msg),
-        ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, env, info, config),
+        ExecuteMsg::SetCollection { cw721 } => execute_set_collection(deps, env, info, cw721),
+        ExecuteMsg::SetFees { fee_bps } => execute_set_fees(deps, env, info, fee_bps),
+        ExecuteMsg::SetTreasury { treasury } => execute_set_treasury(deps, env, info, treasury),
+        ExecuteMsg::SetDenom { denom } => execute_set_denom(deps, env, info, denom),
+        ExecuteMsg::SetUpdateDelay { blocks } => execute_set_update_delay(deps, env, info, blocks),
+        ExecuteMsg::AddNativeDenom { denom } => execute_add_native_denom(deps, env, info, denom),
+        ExecuteMsg::RemoveNativeDenom { denom } => {
+            execute_remove_native_denom(deps, env, info, denom)
+        }
+        ExecuteMsg::AddPaymentToken { address, min_price } => {
+            execute_add_payment_token(deps, env, info, address, min_price)
+        }
+        ExecuteMsg::RemovePaymentToken { address } => {
+            execute_remove_payment_token(deps, env, info, address)
+        }
+        ExecuteMsg::ProposeAdmin { address } => execute_propose_admin(deps, env, info, address),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, env, info),
+        ExecuteMsg::GrantRole { role, address } => {
+            execute_grant_role(deps, env, info, role, address)
+        }
+        ExecuteMsg::RevokeRole { role, address } => {
+            execute_revoke_role(deps, env, info, role, address)
+        }
+        ExecuteMsg::Delist { id, reason } => execute_delist(deps, env, info, id, reason),
+        ExecuteMsg::BlockName { token_id } => execute_block_name(deps, env, info, token_id),
+        ExecuteMsg::UnblockName { token_id } => execute_unblock_name(deps, env, info, token_id),
+        ExecuteMsg::BlockPattern { pattern } => execute_block_pattern(deps, env, info, pattern),
+        ExecuteMsg::UnblockPattern { pattern } => execute_unblock_pattern(deps, env, info, pattern),
+        ExecuteMsg::BlockAddress { address } => execute_block_address(deps, env, info, address),
+        ExecuteMsg::UnblockAddress { address } => execute_unblock_address(deps, env, info, address),
+        ExecuteMsg::PruneBlocked { ids } => execute_prune_blocked(deps, env, info, ids),
+        ExecuteMsg::Pause {} => execute_pause(deps, env, info),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::List { start_after, limit } => to_binary(&query_list(deps, start_after, limit)?),
         QueryMsg::Details { id } => to_binary(&query_details(deps, id)?),
-        QueryMsg::GetTotal { swap_type } => to_binary(&query_swap_total(deps, swap_type)?),
+        QueryMsg::DetailsMany { ids } => to_binary(&query_details_many(deps, ids)?),
+        QueryMsg::GetTotal {
+            swap_type,
+            denom,
+            creator,
+            collection,
+            token_id,
+            include_expired,
+        } => to_binary(&query_swap_total(
+            deps,
+            env,
+            swap_type,
+            denom,
+            creator,
+            collection,
+            token_id,
+            include_expired,
+        )?),
         QueryMsg::GetOffers { page, limit } => {
             to_binary(&query_swaps(deps, SwapType::Offer, page, limit)?)
         }
@@ -101,12 +170,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         )?),
         QueryMsg::SwapsByDenom {
             payment_token,
+            denom,
             swap_type,
             page,
             limit,
         } => to_binary(&query_swaps_by_denom(
             deps,
             payment_token,
+            denom,
             swap_type,
             page,
             limit,
@@ -119,6 +190,81 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         } => to_binary(&query_swaps_by_payment_type(
             deps, cw20, swap_type, page, limit,
         )?),
+        QueryMsg::NativeDenoms { start_after, limit } => {
+            to_binary(&query_native_denoms(deps, start_after, limit)?)
+        }
+        QueryMsg::PaymentTokens { start_after, limit } => {
+            to_binary(&query_payment_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingAdmin {} => to_binary(&query_pending_admin(deps)?),
+        QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+        QueryMsg::BlockedNames { start_after, limit } => {
+            to_binary(&query_blocked_names(deps, start_after, limit)?)
+        }
+        QueryMsg::BlockedPatterns { start_after, limit } => {
+            to_binary(&query_blocked_patterns(deps, start_after, limit)?)
+        }
+        QueryMsg::BlockedAddresses { start_after, limit } => {
+            to_binary(&query_blocked_addresses(deps, start_after, limit)?)
+        }
+        QueryMsg::HasRole { role, address } => to_binary(&query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::Stats {} => to_binary(&query_stats(deps, env)?),
+        QueryMsg::LastSale { token_id } => to_binary(&query_last_sale(deps, token_id)?),
+        QueryMsg::TradeHistory {
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query_trade_history(deps, token_id, start_after, limit)?),
+        QueryMsg::RecentTrades { start_after, limit } => {
+            to_binary(&query_recent_trades(deps, start_after, limit)?)
+        }
+        QueryMsg::UserActivity { address, limit } => {
+            to_binary(&query_user_activity(deps, env, address, limit)?)
+        }
+        QueryMsg::OffersReceived {
+            owner,
+            payment_token,
+            page,
+            limit,
+        } => to_binary(&query_offers_received(
+            deps,
+            env,
+            owner,
+            payment_token,
+            page,
+            limit,
+        )?),
+        QueryMsg::BestPrices { token_id } => to_binary(&query_best_prices(deps, env, token_id)?),
+        QueryMsg::Search {
+            filter,
+            sort,
+            start_after,
+            limit,
+        } => to_binary(&query_search(deps, env, filter, sort, start_after, limit)?),
+        QueryMsg::SearchNames {
+            prefix,
+            contains,
+            tld,
+            min_length,
+            max_length,
+            start_after,
+            limit,
+        } => to_binary(&query_search_names(
+            deps,
+            env,
+            prefix,
+            contains,
+            tld,
+            min_length,
+            max_length,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -126,21 +272,64 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.result {
         SubMsgResult::Ok(_) => Ok(Response::default()),
-        SubMsgResult::Err(_) => Err(ContractError::Unauthorized {}),
+        SubMsgResult::Err(err) => Err(ContractError::SubMsgFailed(err)),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let original_version = get_contract_version(deps.storage)?;
-    let name = CONTRACT_NAME.to_string();
-    let version = CONTRACT_VERSION.to_string();
-    if original_version.contract != name {
+    if original_version.contract != CONTRACT_NAME {
         return Err(ContractError::InvalidInput {});
     }
-    if original_version.version >= version {
+    // Compare semantically, e.g. `1.0.10` is newer than `1.0.8`
+    let original: Version = original_version.version.parse()?;
+    let version: Version = CONTRACT_VERSION.parse()?;
+    if original >= version {
         return Err(ContractError::InvalidInput {});
     }
+
+    // Move the native denom from a compile time constant into config
+    if CONFIG.load(deps.storage).is_err() {
+        let legacy_config = LEGACY_CONFIG.load(deps.storage)?;
+        let denom = msg.denom.ok_or(ContractError::InvalidInput {})?;
+        validate_denom(&denom)?;
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                admin: Some(legacy_config.admin),
+                cw721: legacy_config.cw721,
+                denom: denom.clone(),
+                fee_bps: 0,
+                treasury: None,
+                update_delay: 0,
+            },
+        )?;
+
+        // Native swaps created before multi-denom support settle in the migrated denom
+        let legacy_swaps: Vec<(String, CW721Swap)> = SWAPS
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| match item {
+                Ok((_, swap)) => swap.payment_token.is_none() && swap.denom.is_none(),
+                Err(_) => true,
+            })
+            .collect::<StdResult<Vec<(String, CW721Swap)>>>()?;
+        for (id, mut swap) in legacy_swaps {
+            swap.denom = Some(denom.clone());
+            SWAPS.save(deps.storage, &id, &swap)?;
+        }
+    }
+
+    // Rebuild swap indexes and counts for swaps created before they were tracked
+    let config = CONFIG.load(deps.storage)?;
+    clear_swap_totals(deps.storage)?;
+    let swaps: Vec<(String, CW721Swap)> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, CW721Swap)>>>()?;
+    for (id, swap) in swaps {
+        index_swap(deps.storage, &id, &swap, &config.denom)?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
@@ -148,8 +337,10 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::{LegacyConfig, LISTED_NAMES, LISTING_PRICES, SWAP_TOTALS};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::Expiration;
 
     // Instantiation works
     #[test]
@@ -160,9 +351,106 @@ mod tests {
         let instantiate_msg = InstantiateMsg {
             admin: Addr::unchecked(MOCK_CONTRACT_ADDR),
             cw721: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            denom: "aconst".to_string(),
         };
         let info = mock_info("anyone", &[]);
         let res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
         assert_eq!(0, res.messages.len());
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.denom, "aconst".to_string());
+    }
+
+    // Migrating a deployment with a hard coded denom
+    // moves the denom into config and onto legacy swaps
+    #[test]
+    fn test_migrate_denom() {
+        let mut deps = mock_dependencies();
+
+        // Deployed release without a configurable denom
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.8").unwrap();
+        let legacy_config = LegacyConfig {
+            admin: Addr::unchecked("admin"),
+            cw721: Addr::unchecked(MOCK_CONTRACT_ADDR),
+        };
+        LEGACY_CONFIG
+            .save(&mut deps.storage, &legacy_config)
+            .unwrap();
+        let legacy_swap = CW721Swap {
+            creator: Addr::unchecked("creator"),
+            nft_contract: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            payment_token: None,
+            denom: None,
+            token_id: "petrify".to_string(),
+            expires: Expiration::Never {},
+            price: Uint128::from(1000000000000000000_u128),
+            swap_type: SwapType::Sale,
+            revision: 0,
+            updated_at: None,
+        };
+        SWAPS
+            .save(&mut deps.storage, "firstswap", &legacy_swap)
+            .unwrap();
+
+        // Denom is required to migrate legacy config
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { denom: None }).unwrap_err();
+        assert_eq!(err, ContractError::InvalidInput {});
+
+        let msg = MigrateMsg {
+            denom: Some("aarch".to_string()),
+        };
+        migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.admin, Some(legacy_config.admin));
+        assert_eq!(config.cw721, legacy_config.cw721);
+        assert_eq!(config.denom, "aarch".to_string());
+
+        let swap = SWAPS.load(&deps.storage, "firstswap").unwrap();
+        assert_eq!(swap.denom, Some("aarch".to_string()));
+
+        // Legacy listings are indexed for floor prices
+        let price = swap.price.u128().to_be_bytes().to_vec();
+        assert!(LISTING_PRICES.has(&deps.storage, ("aarch", price, "firstswap")));
+        let total = SWAP_TOTALS
+            .load(&deps.storage, (&swap.nft_contract, SwapType::Sale.as_str()))
+            .unwrap();
+        assert_eq!(total, 1);
+        assert!(LISTED_NAMES.has(&deps.storage, &swap.token_id));
+    }
+
+    // Versions are compared semantically, and only upgrades can migrate
+    #[test]
+    fn test_migrate_version() {
+        let mut deps = mock_dependencies();
+
+        let instantiate_msg = InstantiateMsg {
+            admin: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw721: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            denom: "aarch".to_string(),
+        };
+        let info = mock_info("anyone", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+        // Migrating to the same version fails
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { denom: None }).unwrap_err();
+        assert_eq!(err, ContractError::InvalidInput {});
+
+        // Migrating to an older version fails
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "10.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { denom: None }).unwrap_err();
+        assert_eq!(err, ContractError::InvalidInput {});
+
+        // A pre-release of this version sorts after it as a string, but is older
+        let pre_release = format!("{}-rc.1", CONTRACT_VERSION);
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, pre_release).unwrap();
+        migrate(deps.as_mut(), mock_env(), MigrateMsg { denom: None }).unwrap();
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+
+        // Unparseable versions fail
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "v1").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { denom: None }).unwrap_err();
+        assert!(matches!(err, ContractError::SemVer(_)));
     }
 }
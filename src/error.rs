@@ -1,4 +1,6 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Uint128};
+
+use crate::state::SwapType;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +11,9 @@ pub enum ContractError {
     #[error("Hash parse error: {0}")]
     ParseError(String),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     #[error("Invalid atomic swap id")]
     InvalidId {},
 
@@ -33,6 +38,88 @@ pub enum ContractError {
     #[error("Insufficient contract balance")]
     InsufficientBalance {},
 
+    #[error("Insufficient funds: expected {expected}, sent {sent}")]
+    InsufficientFunds { expected: Coin, sent: Coin },
+
+    #[error("Native denom {denom} is not accepted for payment")]
+    DenomNotAllowed { denom: String },
+
+    #[error("cw20 token {token} is not accepted for payment")]
+    PaymentTokenNotAllowed { token: String },
+
+    #[error("Price {price} is below the minimum price of {min_price}")]
+    PriceTooLow { price: Uint128, min_price: Uint128 },
+
+    #[error("Invalid native denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Invalid fee: {fee_bps} basis points exceeds the maximum of {max_fee_bps}")]
+    InvalidFee { fee_bps: u64, max_fee_bps: u64 },
+
+    #[error("Invalid update delay: {blocks} blocks exceeds the maximum of {max_blocks}")]
+    InvalidUpdateDelay { blocks: u64, max_blocks: u64 },
+
+    #[error("{address} is not a cw721 collection")]
+    InvalidCollection { address: String },
+
+    #[error("Wrong denom: expected {expected}, sent {sent}")]
+    WrongDenom { expected: String, sent: String },
+
+    #[error("Insufficient allowance for {token}: expected {expected}, approved {allowance}")]
+    InsufficientAllowance {
+        token: String,
+        expected: Uint128,
+        allowance: Uint128,
+    },
+
+    #[error("Token {token_id} not found")]
+    TokenNotFound { token_id: String },
+
+    #[error("{sender} is not the owner of token {token_id}")]
+    NotTokenOwner { sender: String, token_id: String },
+
+    #[error("{sender} is not the creator of swap {id}")]
+    NotSwapCreator { sender: String, id: String },
+
+    #[error("Marketplace is not approved to transfer token {token_id}")]
+    MissingApproval { token_id: String },
+
+    #[error("Unsupported swap type: {swap_type:?} swaps cannot be paid in native {denom}")]
+    UnsupportedSwapType { swap_type: SwapType, denom: String },
+
+    #[error("Token {token_id} is blocked from listing")]
+    NameBlocked { token_id: String },
+
+    #[error("Address {address} is blocked")]
+    AddressBlocked { address: String },
+
+    #[error("Swap price {price} does not satisfy the expected price of {expected_price}")]
+    UnexpectedPrice {
+        price: Uint128,
+        expected_price: Uint128,
+    },
+
+    #[error("Swap payment token {payment_token} does not match the expected {expected}")]
+    UnexpectedPaymentToken {
+        payment_token: String,
+        expected: String,
+    },
+
+    #[error("Swap revision {revision} does not match the expected revision {expected_revision}")]
+    UnexpectedRevision {
+        revision: u64,
+        expected_revision: u64,
+    },
+
+    #[error("Swap was updated recently and cannot be finished before block {height}")]
+    UpdatePending { height: u64 },
+
+    #[error("Marketplace is paused")]
+    Paused {},
+
+    #[error("Submessage failed: {0}")]
+    SubMsgFailed(String),
+
     #[error("Unauthorized")]
     Unauthorized,
     #[error("Expired atomic swap")]
@@ -44,3 +131,9 @@ pub enum ContractError {
     #[error("Atomic swap already exists")]
     AlreadyExists,
 }
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{Addr, Coin, Event, Uint128};
+
+use crate::state::CW721Swap;
+
+// Swap lifecycle events, emitted as `wasm-archid_market_<action>`
+// (see README.md for the full schema)
+pub const CREATE_EVENT: &str = "archid_market_create";
+pub const UPDATE_EVENT: &str = "archid_market_update";
+pub const CANCEL_EVENT: &str = "archid_market_cancel";
+pub const FINISH_EVENT: &str = "archid_market_finish";
+pub const DELIST_EVENT: &str = "archid_market_delist";
+pub const PRUNE_EVENT: &str = "archid_market_prune";
+// Other swaps of a token removed when the token is sold
+pub const INVALIDATE_EVENT: &str = "archid_market_invalidate";
+
+// Full details of a swap, shared by all swap events
+fn swap_event(ty: &str, id: &str, swap: &CW721Swap, default_denom: &str) -> Event {
+    let (payment_type, payment_token) = match &swap.payment_token {
+        Some(token) => ("cw20", token.to_string()),
+        None => ("native", swap.native_denom(default_denom)),
+    };
+    Event::new(ty)
+        .add_attribute("swap_id", id)
+        .add_attribute("creator", swap.creator.as_str())
+        .add_attribute("nft_contract", swap.nft_contract.as_str())
+        .add_attribute("token_id", &swap.token_id)
+        .add_attribute("swap_type", swap.swap_type.as_str())
+        .add_attribute("payment_type", payment_type)
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("price", swap.price)
+        .add_attribute("expires", swap.expires.to_string())
+}
+
+pub fn create_event(id: &str, swap: &CW721Swap, default_denom: &str) -> Event {
+    swap_event(CREATE_EVENT, id, swap, default_denom)
+}
+
+pub fn update_event(
+    id: &str,
+    swap: &CW721Swap,
+    default_denom: &str,
+    previous: &CW721Swap,
+) -> Event {
+    swap_event(UPDATE_EVENT, id, swap, default_denom)
+        .add_attribute("revision", swap.revision.to_string())
+        .add_attribute("old_price", previous.price)
+        .add_attribute("old_expires", previous.expires.to_string())
+}
+
+pub fn cancel_event(id: &str, swap: &CW721Swap, default_denom: &str) -> Event {
+    swap_event(CANCEL_EVENT, id, swap, default_denom)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn finish_event(
+    id: &str,
+    swap: &CW721Swap,
+    default_denom: &str,
+    seller: &Addr,
+    buyer: &Addr,
+    fee: Uint128,
+    treasury: Option<&Addr>,
+    refund: &[Coin],
+) -> Event {
+    let treasury: String = treasury
+        .map(|treasury| treasury.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    let refund: String = if refund.is_empty() {
+        "0".to_string()
+    } else {
+        refund
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+
+    swap_event(FINISH_EVENT, id, swap, default_denom)
+        .add_attribute("seller", seller.as_str())
+        .add_attribute("buyer", buyer.as_str())
+        .add_attribute("fee", fee)
+        .add_attribute("treasury", treasury)
+        .add_attribute("seller_amount", swap.price - fee)
+        .add_attribute("refund", refund)
+}
+
+pub fn delist_event(
+    id: &str,
+    swap: &CW721Swap,
+    default_denom: &str,
+    moderator: &Addr,
+    reason: &str,
+) -> Event {
+    swap_event(DELIST_EVENT, id, swap, default_denom)
+        .add_attribute("moderator", moderator.as_str())
+        .add_attribute("reason", reason)
+}
+
+pub fn prune_event(id: &str, swap: &CW721Swap, default_denom: &str) -> Event {
+    swap_event(PRUNE_EVENT, id, swap, default_denom)
+}
+
+pub fn invalidate_event(id: &str, swap: &CW721Swap, default_denom: &str, sold_id: &str) -> Event {
+    swap_event(INVALIDATE_EVENT, id, swap, default_denom).add_attribute("sold_swap_id", sold_id)
+}
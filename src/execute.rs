@@ -1,14 +1,29 @@
 use cosmwasm_std::{
-    to_binary, Coin, DepsMut, Env, MessageInfo, Order, QueryRequest, Response, WasmQuery,
+    to_binary, Addr, BankMsg, Coin, DepsMut, Empty, Env, Event, MessageInfo, Order, Response,
+    StdResult, Uint128,
 };
 
-use cw721::OwnerOfResponse;
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use cw721::ContractInfoResponse;
+use cw721_base::QueryMsg as Cw721QueryMsg;
 
-use crate::contract::DENOM;
 use crate::error::ContractError;
-use crate::msg::{CancelMsg, SwapMsg, UpdateMsg};
-use crate::state::{CW721Swap, Config, SwapType, CONFIG, SWAPS};
-use crate::utils::{check_sent_required_payment, handle_swap_transfers, query_name_owner};
+use crate::events::{
+    cancel_event, create_event, delist_event, finish_event, invalidate_event, prune_event,
+    update_event,
+};
+use crate::msg::{CancelMsg, CreateResponse, FinishMsg, SwapMsg, UpdateMsg};
+use crate::state::{
+    index_swap, is_name_blocked, is_native_denom_allowed, next_swap_id, record_sale, unindex_swap,
+    CW721Swap, PaymentToken, Role, SwapType, BLOCKED_ADDRESSES, BLOCKED_NAMES, BLOCKED_PATTERNS,
+    CONFIG, NATIVE_DENOMS, PAUSED, PAYMENT_TOKENS, PENDING_ADMIN, ROLES, SWAPS, SWAPS_BY_TOKEN,
+};
+use crate::utils::{
+    calculate_fee, calculate_refund, check_admin, check_cw20_allowance, check_nft_approval,
+    check_not_blocked, check_not_paused, check_payment_token, check_role,
+    check_sent_required_payment, handle_swap_transfers, query_name_owner, validate_denom,
+    MAX_FEE_BPS, MAX_UPDATE_DELAY,
+};
 
 pub fn execute_create(
     deps: DepsMut,
@@ -16,62 +31,122 @@ pub fn execute_create(
     info: MessageInfo,
     msg: SwapMsg,
 ) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage)?;
     if msg.expires.is_expired(&env.block) {
         return Err(ContractError::Expired {});
     }
+    check_not_blocked(deps.storage, &info.sender)?;
+    if is_name_blocked(deps.storage, &msg.token_id)? {
+        return Err(ContractError::NameBlocked {
+            token_id: msg.token_id,
+        });
+    }
+
+    // Numeric ids are reserved for auto-generated ids
+    let id = match msg.id {
+        Some(id) => {
+            if id.is_empty() || id.chars().all(|c| c.is_ascii_digit()) {
+                return Err(ContractError::InvalidId {});
+            }
+            id
+        }
+        None => next_swap_id(deps.storage)?,
+    };
 
     let config = CONFIG.load(deps.storage)?;
     let has_payment_token = msg.payment_token.is_some();
+    // Native swaps settle in the requested (allowlisted)
+    // native denom, or default to the configured native denom
+    let denom: Option<String> = if let Some(payment_token) = &msg.payment_token {
+        if msg.denom.is_some() {
+            return Err(ContractError::InvalidPaymentToken {});
+        }
+        check_payment_token(&deps, payment_token, msg.price)?;
+        None
+    } else {
+        let denom = msg.denom.unwrap_or_else(|| config.denom.clone());
+        if !is_native_denom_allowed(deps.storage, &denom, &config.denom) {
+            return Err(ContractError::DenomNotAllowed { denom });
+        }
+        Some(denom)
+    };
     // SwapType::Sale
     if msg.swap_type == SwapType::Sale {
-        let owner = query_name_owner(&msg.token_id, &config.cw721, &deps).unwrap();
+        let owner = query_name_owner(&msg.token_id, &config.cw721, &deps).map_err(|_| {
+            ContractError::TokenNotFound {
+                token_id: msg.token_id.clone(),
+            }
+        })?;
         if owner.owner != info.sender {
-            return Err(ContractError::Unauthorized {});
+            return Err(ContractError::NotTokenOwner {
+                sender: info.sender.to_string(),
+                token_id: msg.token_id,
+            });
         }
     // SwapType::Offer
     } else if msg.swap_type == SwapType::Offer && !has_payment_token {
-        return Err(ContractError::InvalidPaymentToken {});
+        return Err(ContractError::UnsupportedSwapType {
+            swap_type: msg.swap_type,
+            denom: denom.unwrap_or_default(),
+        });
     }
     let swap = CW721Swap {
         creator: info.sender,
         nft_contract: config.cw721,
         payment_token: msg.payment_token,
+        denom,
         token_id: msg.token_id,
         expires: msg.expires,
         price: msg.price,
         swap_type: msg.swap_type,
+        revision: 0,
+        updated_at: None,
     };
 
     // Try to store it, fail if the id already exists (unmodifiable swaps)
-    SWAPS.update(deps.storage, &msg.id, |existing| match existing {
+    SWAPS.update(deps.storage, &id, |existing| match existing {
         None => Ok(swap.clone()),
         Some(_) => Err(ContractError::AlreadyExists {}),
     })?;
+    index_swap(deps.storage, &id, &swap, &config.denom)?;
 
-    let payment_token: String = if has_payment_token {
-        swap.payment_token.unwrap().to_string()
-    } else {
-        DENOM.to_string()
+    let payment_token: String = match &swap.payment_token {
+        Some(token) => token.to_string(),
+        None => swap.native_denom(&config.denom),
     };
 
     Ok(Response::new()
+        .set_data(to_binary(&CreateResponse { id: id.clone() })?)
         .add_attribute("action", "create")
-        .add_attribute("token_id", swap.token_id)
+        .add_attribute("swap_id", &id)
+        .add_attribute("token_id", &swap.token_id)
         .add_attribute("payment_token", payment_token)
-        .add_attribute("price", swap.price))
+        .add_attribute("price", swap.price)
+        .add_event(create_event(&id, &swap, &config.denom)))
 }
 
 pub fn execute_update(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: UpdateMsg,
 ) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
     let swap = SWAPS.load(deps.storage, &msg.id)?;
+    let previous = swap.clone();
 
     // Only creator can update swap
     if info.sender != swap.creator {
-        return Err(ContractError::Unauthorized {});
+        return Err(ContractError::NotSwapCreator {
+            sender: info.sender.to_string(),
+            id: msg.id,
+        });
+    }
+
+    // Updated price must respect the token's minimum price
+    if let Some(payment_token) = &swap.payment_token {
+        check_payment_token(&deps, payment_token, msg.price)?;
     }
 
     // For security reasons, creator, nft_contract, token_id,
@@ -81,92 +156,199 @@ pub fn execute_update(
         creator: swap.creator,
         nft_contract: swap.nft_contract,
         payment_token: swap.payment_token,
+        denom: swap.denom,
         token_id: swap.token_id,
         expires: msg.expires,
         price: msg.price,
         swap_type: swap.swap_type,
+        revision: swap.revision + 1,
+        updated_at: Some(env.block.height),
     };
     // Remove legacy swap and save updated swap
     SWAPS.remove(deps.storage, &msg.id);
     SWAPS.save(deps.storage, &msg.id, &swap)?;
+    unindex_swap(deps.storage, &msg.id, &previous, &config.denom)?;
+    index_swap(deps.storage, &msg.id, &swap, &config.denom)?;
 
     Ok(Response::new()
         .add_attribute("action", "update")
         .add_attribute("swap_id", &msg.id)
-        .add_attribute("token_id", &swap.token_id))
+        .add_attribute("token_id", &swap.token_id)
+        .add_attribute("revision", swap.revision.to_string())
+        .add_event(update_event(&msg.id, &swap, &config.denom, &previous)))
 }
 
 pub fn execute_finish(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: SwapMsg,
+    msg: FinishMsg,
 ) -> Result<Response, ContractError> {
-    let swap = SWAPS.load(deps.storage, &msg.id)?;
+    check_not_paused(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let id = msg.id;
+    let swap = SWAPS.load(deps.storage, &id)?;
 
     if swap.expires.is_expired(&env.block) {
         return Err(ContractError::Expired {});
     }
 
-    // If swapping for native `aarch`
+    // Recently updated swaps cannot be taken until the update delay has passed
+    if let Some(updated_at) = swap.updated_at {
+        let height = updated_at.saturating_add(config.update_delay);
+        if env.block.height < height {
+            return Err(ContractError::UpdatePending { height });
+        }
+    }
+
+    // Swap terms must still match the caller's expectations
+    if let Some(expected_revision) = msg.expected_revision {
+        if swap.revision != expected_revision {
+            return Err(ContractError::UnexpectedRevision {
+                revision: swap.revision,
+                expected_revision,
+            });
+        }
+    }
+    let payment_token: String = match &swap.payment_token {
+        Some(token) => token.to_string(),
+        None => swap.native_denom(&config.denom),
+    };
+    if payment_token != msg.expected_payment_token {
+        return Err(ContractError::UnexpectedPaymentToken {
+            payment_token,
+            expected: msg.expected_payment_token,
+        });
+    }
+    let price_ok = match swap.swap_type {
+        SwapType::Sale => swap.price <= msg.expected_price,
+        SwapType::Offer => swap.price >= msg.expected_price,
+    };
+    if !price_ok {
+        return Err(ContractError::UnexpectedPrice {
+            price: swap.price,
+            expected_price: msg.expected_price,
+        });
+    }
+
+    // If swapping for a native denom
     // check payment conditions satisfied
     if swap.payment_token.is_none() {
+        // Native offers not allowed
+        if swap.swap_type == SwapType::Offer {
+            return Err(ContractError::UnsupportedSwapType {
+                denom: swap.native_denom(&config.denom),
+                swap_type: swap.swap_type,
+            });
+        }
+
         let required_payment = Coin {
-            denom: DENOM.to_string(),
+            denom: swap.native_denom(&config.denom),
             amount: swap.price,
         };
         check_sent_required_payment(&info.funds, Some(required_payment))?;
+    }
 
-        // Native aarch offers not allowed
-        if swap.swap_type == SwapType::Offer {
-            return Err(ContractError::InvalidInput {});
+    // Sales are sold by their creator, offers are accepted by the token owner
+    let (seller, buyer) = match swap.swap_type {
+        SwapType::Offer => (info.sender.clone(), swap.creator.clone()),
+        SwapType::Sale => (swap.creator.clone(), info.sender.clone()),
+    };
+    check_not_blocked(deps.storage, &seller)?;
+    check_not_blocked(deps.storage, &buyer)?;
+
+    let owner_of = query_name_owner(&swap.token_id, &swap.nft_contract, &deps).map_err(|_| {
+        ContractError::TokenNotFound {
+            token_id: swap.token_id.clone(),
         }
+    })?;
+    if owner_of.owner != seller {
+        return Err(ContractError::NotTokenOwner {
+            sender: seller.to_string(),
+            token_id: swap.token_id,
+        });
+    }
+    check_nft_approval(&deps, &env, &swap.nft_contract, &swap.token_id, &seller)?;
+    if let Some(payment_token) = &swap.payment_token {
+        check_cw20_allowance(&deps, &env, payment_token, &buyer, swap.price)?;
     }
 
-    let transfer_results = match msg.swap_type {
-        SwapType::Offer => {
-            let owner_of: OwnerOfResponse =
-                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                    contract_addr: swap.nft_contract.to_string(),
-                    msg: to_binary(&cw721::Cw721QueryMsg::OwnerOf {
-                        token_id: swap.token_id.clone(),
-                        include_expired: None,
-                    })?,
-                }))?;
-
-            if owner_of.owner != info.sender {
-                return Err(ContractError::Unauthorized {});
-            }
+    let mut transfer_results = handle_swap_transfers(&seller, &buyer, swap.clone(), &config)?;
+    let fee = calculate_fee(&config, swap.price);
 
-            handle_swap_transfers(&info.sender, &swap.creator, swap.clone(), &info.funds)?
-        }
-        SwapType::Sale => {
-            handle_swap_transfers(&swap.creator, &info.sender, swap.clone(), &info.funds)?
-        }
+    // Refund anything sent in excess of the swap price
+    let payment: Option<Coin> = match swap.payment_token {
+        Some(_) => None,
+        None => Some(Coin {
+            denom: swap.native_denom(&config.denom),
+            amount: swap.price,
+        }),
     };
+    let refund = calculate_refund(&info.funds, payment.as_ref());
+    let refund_attr: String = if refund.is_empty() {
+        "0".to_string()
+    } else {
+        refund
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    let finish_event = finish_event(
+        &id,
+        &swap,
+        &config.denom,
+        &seller,
+        &buyer,
+        fee,
+        config.treasury.as_ref(),
+        &refund,
+    );
+    if !refund.is_empty() {
+        transfer_results.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: refund,
+            }
+            .into(),
+        );
+    }
 
     // Remove all swaps for this token_id
     // (as they're no longer valid)
-    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect();
-    for swap in swaps.unwrap().iter() {
-        if swap.1.token_id == msg.token_id {
-            SWAPS.remove(deps.storage, &swap.0);
+    let swap_ids: Vec<String> = SWAPS_BY_TOKEN
+        .prefix(&swap.token_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    let mut invalidate_events: Vec<Event> = vec![];
+    for swap_id in swap_ids {
+        let token_swap = SWAPS.load(deps.storage, &swap_id)?;
+        SWAPS.remove(deps.storage, &swap_id);
+        unindex_swap(deps.storage, &swap_id, &token_swap, &config.denom)?;
+        if swap_id != id {
+            invalidate_events.push(invalidate_event(&swap_id, &token_swap, &config.denom, &id));
         }
     }
 
-    let payment_token: String = if msg.payment_token.is_some() {
-        msg.payment_token.unwrap().to_string()
-    } else {
-        DENOM.to_string()
-    };
+    record_sale(
+        deps.storage,
+        &id,
+        &swap,
+        &config.denom,
+        &seller,
+        &buyer,
+        &env.block,
+    )?;
 
     Ok(Response::new()
         .add_attribute("action", "finish")
-        .add_attribute("token_id", msg.token_id)
+        .add_attribute("swap_id", &id)
+        .add_attribute("token_id", &swap.token_id)
         .add_attribute("payment_token", payment_token)
-        .add_attribute("price", msg.price)
+        .add_attribute("price", swap.price)
+        .add_attribute("refund", refund_attr)
+        .add_event(finish_event)
+        .add_events(invalidate_events)
         .add_messages(transfer_results))
 }
 
@@ -178,29 +360,545 @@ pub fn execute_cancel(
 ) -> Result<Response, ContractError> {
     let swap = SWAPS.load(deps.storage, &msg.id)?;
     if info.sender != swap.creator {
-        return Err(ContractError::Unauthorized {});
+        return Err(ContractError::NotSwapCreator {
+            sender: info.sender.to_string(),
+            id: msg.id,
+        });
     }
 
+    let config = CONFIG.load(deps.storage)?;
     SWAPS.remove(deps.storage, &msg.id);
+    unindex_swap(deps.storage, &msg.id, &swap, &config.denom)?;
 
     Ok(Response::new()
         .add_attribute("action", "cancel")
-        .add_attribute("swap_id", msg.id))
+        .add_attribute("swap_id", &msg.id)
+        .add_attribute("token_id", &swap.token_id)
+        .add_event(cancel_event(&msg.id, &swap, &config.denom)))
+}
+
+pub fn execute_delist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Moderator, &info.sender)?;
+
+    // Swaps hold no escrow (payment and NFT move on `Finish`),
+    // so there is nothing to refund
+    let swap = SWAPS.load(deps.storage, &id)?;
+    SWAPS.remove(deps.storage, &id);
+    unindex_swap(deps.storage, &id, &swap, &config.denom)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delist")
+        .add_attribute("swap_id", &id)
+        .add_attribute("token_id", &swap.token_id)
+        .add_attribute("creator", &swap.creator)
+        .add_attribute("moderator", &info.sender)
+        .add_attribute("reason", &reason)
+        .add_event(delist_event(
+            &id,
+            &swap,
+            &config.denom,
+            &info.sender,
+            &reason,
+        )))
 }
 
-pub fn execute_update_config(
+pub fn execute_block_name(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    config_update: Config,
+    token_id: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Moderator, &info.sender)?;
+
+    if token_id.is_empty() {
+        return Err(ContractError::InvalidInput {});
+    }
+    BLOCKED_NAMES.save(deps.storage, &token_id, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "block_name")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_unblock_name(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Moderator, &info.sender)?;
+
+    BLOCKED_NAMES.remove(deps.storage, &token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "unblock_name")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_block_pattern(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pattern: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Moderator, &info.sender)?;
+
+    // An empty pattern would match (and block) every name
+    if pattern.is_empty() {
+        return Err(ContractError::InvalidInput {});
+    }
+    let pattern = pattern.to_lowercase();
+    BLOCKED_PATTERNS.save(deps.storage, &pattern, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "block_pattern")
+        .add_attribute("pattern", pattern))
+}
+
+pub fn execute_unblock_pattern(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pattern: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Moderator, &info.sender)?;
+
+    let pattern = pattern.to_lowercase();
+    BLOCKED_PATTERNS.remove(deps.storage, &pattern);
+
+    Ok(Response::new()
+        .add_attribute("action", "unblock_pattern")
+        .add_attribute("pattern", pattern))
+}
+
+pub fn execute_block_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    BLOCKED_ADDRESSES.save(deps.storage, &address, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "block_address")
+        .add_attribute("address", address))
+}
+
+pub fn execute_unblock_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    BLOCKED_ADDRESSES.remove(deps.storage, &address);
+
+    Ok(Response::new()
+        .add_attribute("action", "unblock_address")
+        .add_attribute("address", address))
+}
+
+pub fn execute_prune_blocked(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut pruned: Vec<String> = vec![];
+    let mut prune_events: Vec<Event> = vec![];
+    for id in ids {
+        if let Some(swap) = SWAPS.may_load(deps.storage, &id)? {
+            if BLOCKED_ADDRESSES.has(deps.storage, &swap.creator) {
+                SWAPS.remove(deps.storage, &id);
+                unindex_swap(deps.storage, &id, &swap, &config.denom)?;
+                prune_events.push(prune_event(&id, &swap, &config.denom));
+                pruned.push(id);
+            }
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_blocked")
+        .add_attribute("pruned", pruned.len().to_string())
+        .add_attribute("swap_ids", pruned.join(","))
+        .add_events(prune_events))
+}
+
+pub fn execute_set_collection(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    cw721: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    // Collection must be a cw721 contract
+    let cw721 = deps.api.addr_validate(&cw721)?;
+    let _contract_info: ContractInfoResponse = deps
+        .querier
+        .query_wasm_smart(&cw721, &Cw721QueryMsg::ContractInfo {})
+        .map_err(|_| ContractError::InvalidCollection {
+            address: cw721.to_string(),
+        })?;
+
+    let old_cw721 = config.cw721;
+    config.cw721 = cw721.clone();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_collection")
+        .add_attribute("old_cw721", old_cw721)
+        .add_attribute("new_cw721", cw721))
+}
+
+pub fn execute_set_fees(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    fee_bps: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Operator, &info.sender)?;
+
+    if fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFee {
+            fee_bps,
+            max_fee_bps: MAX_FEE_BPS,
+        });
+    }
+
+    let old_fee_bps = config.fee_bps;
+    config.fee_bps = fee_bps;
+    CONFIG.save(deps.storage, &config)?;
 
-    if config.admin != info.sender {
+    Ok(Response::new()
+        .add_attribute("action", "set_fees")
+        .add_attribute("old_fee_bps", old_fee_bps.to_string())
+        .add_attribute("new_fee_bps", fee_bps.to_string()))
+}
+
+pub fn execute_set_treasury(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    treasury: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    let treasury = deps.api.addr_validate(&treasury)?;
+
+    let old_treasury: String = config
+        .treasury
+        .as_ref()
+        .map(|treasury| treasury.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    config.treasury = Some(treasury.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_treasury")
+        .add_attribute("old_treasury", old_treasury)
+        .add_attribute("new_treasury", treasury))
+}
+
+pub fn execute_set_denom(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    validate_denom(&denom)?;
+
+    let old_denom = config.denom;
+    config.denom = denom.clone();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_denom")
+        .add_attribute("old_denom", old_denom)
+        .add_attribute("new_denom", denom))
+}
+
+pub fn execute_set_update_delay(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    blocks: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    if blocks > MAX_UPDATE_DELAY {
+        return Err(ContractError::InvalidUpdateDelay {
+            blocks,
+            max_blocks: MAX_UPDATE_DELAY,
+        });
+    }
+
+    let old_update_delay = config.update_delay;
+    config.update_delay = blocks;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_update_delay")
+        .add_attribute("old_update_delay", old_update_delay.to_string())
+        .add_attribute("new_update_delay", blocks.to_string()))
+}
+
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    let pending_admin = deps.api.addr_validate(&address)?;
+    PENDING_ADMIN.save(deps.storage, &pending_admin)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("admin", info.sender)
+        .add_attribute("pending_admin", pending_admin))
+}
+
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Only the proposed admin can accept
+    let pending_admin = PENDING_ADMIN.may_load(deps.storage)?;
+    if pending_admin.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
-    CONFIG.save(deps.storage, &config_update)?;
+    let previous_admin: String = config
+        .admin
+        .as_ref()
+        .map(|admin| admin.to_string())
+        .unwrap_or_default();
+    config.admin = Some(info.sender.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous_admin)
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_renounce_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+    // Nobody could unpause or revoke roles once the admin is gone
+    check_not_paused(deps.storage)?;
+
+    // Irreversible, admin only functions are disabled for good
+    config.admin = None;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    let members: Vec<(String, Addr)> = ROLES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (role, address) in members {
+        ROLES.remove(deps.storage, (&role, &address));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender))
+}
+
+pub fn execute_add_native_denom(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    check_admin(&config, &info.sender)?;
+    validate_denom(&denom)?;
+
+    NATIVE_DENOMS.save(deps.storage, &denom, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_native_denom")
+        .add_attribute("denom", denom))
+}
+
+pub fn execute_remove_native_denom(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    check_admin(&config, &info.sender)?;
+    if !NATIVE_DENOMS.has(deps.storage, &denom) {
+        return Err(ContractError::DenomNotAllowed { denom });
+    }
+
+    NATIVE_DENOMS.remove(deps.storage, &denom);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_native_denom")
+        .add_attribute("denom", denom))
+}
+
+pub fn execute_add_payment_token(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+    min_price: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    check_admin(&config, &info.sender)?;
+
+    // Token must be a cw20 contract
+    let token = deps.api.addr_validate(&address)?;
+    let _token_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(&token, &Cw20QueryMsg::TokenInfo {})
+        .map_err(|_| ContractError::InvalidPaymentToken {})?;
+
+    PAYMENT_TOKENS.save(deps.storage, &token, &PaymentToken { min_price })?;
+
+    let min_price_attr: String = match min_price {
+        Some(price) => price.to_string(),
+        None => "none".to_string(),
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "add_payment_token")
+        .add_attribute("payment_token", token)
+        .add_attribute("min_price", min_price_attr))
+}
+
+pub fn execute_remove_payment_token(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    check_admin(&config, &info.sender)?;
+
+    let token = deps.api.addr_validate(&address)?;
+    if !PAYMENT_TOKENS.has(deps.storage, &token) {
+        return Err(ContractError::PaymentTokenNotAllowed {
+            token: token.to_string(),
+        });
+    }
+
+    PAYMENT_TOKENS.remove(deps.storage, &token);
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+    Ok(Response::new()
+        .add_attribute("action", "remove_payment_token")
+        .add_attribute("payment_token", token))
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.save(deps.storage, (role.as_str(), &address), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_admin(&config, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Pauser, &info.sender)
+        .or_else(|_| check_role(deps.storage, &config, &Role::Guardian, &info.sender))?;
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_role(deps.storage, &config, &Role::Guardian, &info.sender)?;
+
+    PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("sender", info.sender))
 }
@@ -0,0 +1,163 @@
+#![cfg(test)]
+use cosmwasm_std::Addr;
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{create_cw721, create_swap, mock_app, query};
+use crate::msg::{ExecuteMsg, HasRoleResponse, PendingAdminResponse, QueryMsg};
+use crate::state::{Config, Role};
+use crate::ContractError;
+
+// Admin role is transferred in two steps (propose / accept)
+// and can be renounced for good
+#[test]
+fn test_admin_transfer_and_renounce() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // new_admin will take over the admin role
+    let new_admin = Addr::unchecked("new_admin");
+    // random cannot accept the admin role
+    let random = Addr::unchecked("random");
+    // pauser is granted the pauser role
+    let pauser = Addr::unchecked("pauser");
+
+    // swap_admin creates the swap contract
+    let nft = create_cw721(&mut app, &swap_admin);
+    let swap_inst = create_swap(&mut app, &swap_admin, nft);
+
+    // Only the admin can propose a new admin
+    let propose_msg = ExecuteMsg::ProposeAdmin {
+        address: new_admin.to_string(),
+    };
+    let err = app
+        .execute_contract(random.clone(), swap_inst.clone(), &propose_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(swap_admin.clone(), swap_inst.clone(), &propose_msg, &[])
+        .unwrap();
+
+    let pending_query: PendingAdminResponse =
+        query(&mut app, swap_inst.clone(), QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(pending_query.pending_admin, Some(new_admin.clone()));
+
+    // Only the pending admin can accept
+    let err = app
+        .execute_contract(random, swap_inst.clone(), &ExecuteMsg::AcceptAdmin {}, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(
+            new_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::AcceptAdmin {},
+            &[],
+        )
+        .unwrap();
+
+    let pending_query: PendingAdminResponse =
+        query(&mut app, swap_inst.clone(), QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(pending_query.pending_admin, None);
+    let config_query: Config = query(&mut app, swap_inst.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config_query.admin, Some(new_admin.clone()));
+
+    // Previous admin has lost admin rights, the new admin has them
+    let add_denom_msg = ExecuteMsg::AddNativeDenom {
+        denom: "uatom".to_string(),
+    };
+    let err = app
+        .execute_contract(swap_admin, swap_inst.clone(), &add_denom_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(new_admin.clone(), swap_inst.clone(), &add_denom_msg, &[])
+        .unwrap();
+
+    // Renouncing is rejected while paused, since nobody could unpause
+    let _res = app
+        .execute_contract(
+            new_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                address: pauser.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            pauser.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap();
+    let err = app
+        .execute_contract(
+            new_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::RenounceAdmin {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Paused {}
+    );
+    let _res = app
+        .execute_contract(
+            new_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Unpause {},
+            &[],
+        )
+        .unwrap();
+
+    // New admin renounces, admin only functions are disabled for good
+    // and granted roles are revoked
+    let _res = app
+        .execute_contract(
+            new_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::RenounceAdmin {},
+            &[],
+        )
+        .unwrap();
+    let err = app
+        .execute_contract(new_admin, swap_inst.clone(), &add_denom_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let config_query: Config = query(&mut app, swap_inst.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config_query.admin, None);
+    let role_query: HasRoleResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::HasRole {
+            role: Role::Pauser,
+            address: pauser.to_string(),
+        },
+    )
+    .unwrap();
+    assert!(!role_query.has_role);
+    let err = app
+        .execute_contract(pauser, swap_inst, &ExecuteMsg::Pause {}, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+}
@@ -0,0 +1,166 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query, DENOM,
+};
+use crate::msg::{BestPricesResponse, ExecuteMsg, QueryMsg, SwapMsg};
+use crate::state::SwapType;
+
+// Best prices skip expired swaps, sales from previous owners
+// and swaps from blocked addresses
+#[test]
+fn test_best_prices() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // new_owner receives the cw721
+    let new_owner = Addr::unchecked("new_owner");
+    // cw20_owner owns the cw20
+    let cw20_owner = Addr::unchecked("cw20_owner");
+    // bidder is blocked after bidding
+    let bidder = Addr::unchecked("bidder");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw20_owner creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(100000_u32),
+    );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // cw721_owner lists for 1 ARCH, then transfers the token to new_owner,
+    // who lists it for 3 and 2 ARCH
+    let creation_msg = SwapMsg {
+        id: Some("staleswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id: token_id.clone(),
+        expires: Expiration::Never {},
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap();
+    let transfer_msg = Cw721ExecuteMsg::<Extension>::TransferNft {
+        recipient: new_owner.to_string(),
+        token_id: token_id.clone(),
+    };
+    let _res = app
+        .execute_contract(cw721_owner, nft, &transfer_msg, &[])
+        .unwrap();
+    for (id, price) in [
+        ("highswap", 3000000000000000000_u128),
+        ("lowswap", 2000000000000000000_u128),
+    ] {
+        let _res = app
+            .execute_contract(
+                new_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(SwapMsg {
+                    id: Some(id.to_string()),
+                    price: Uint128::from(price),
+                    ..creation_msg.clone()
+                }),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Bids of 100 and 200 (by bidder), and 300 expiring at the next block
+    let height = app.block_info().height;
+    for (id, creator, price, expires) in [
+        ("lowoffer", &cw20_owner, 100_u32, Expiration::Never {}),
+        ("blockedoffer", &bidder, 200_u32, Expiration::Never {}),
+        (
+            "expiredoffer",
+            &cw20_owner,
+            300_u32,
+            Expiration::AtHeight(height + 1),
+        ),
+    ] {
+        let offer_msg = SwapMsg {
+            id: Some(id.to_string()),
+            payment_token: Some(cw20.clone()),
+            denom: None,
+            token_id: token_id.clone(),
+            expires,
+            price: Uint128::from(price),
+            swap_type: SwapType::Offer,
+        };
+        let _res = app
+            .execute_contract(
+                creator.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(offer_msg),
+                &[],
+            )
+            .unwrap();
+    }
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::BlockAddress {
+                address: bidder.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    let prices_query: BestPricesResponse =
+        query(&mut app, swap_inst, QueryMsg::BestPrices { token_id }).unwrap();
+    let prices: Vec<(String, Option<String>, Option<String>)> = prices_query
+        .prices
+        .into_iter()
+        .map(|best| {
+            (
+                best.payment_token,
+                best.best_ask.map(|ask| ask.id),
+                best.best_bid.map(|bid| bid.id),
+            )
+        })
+        .collect();
+    assert_eq!(
+        prices,
+        vec![
+            (DENOM.to_string(), Some("lowswap".to_string()), None),
+            (cw20.to_string(), None, Some("lowoffer".to_string())),
+        ]
+    );
+}
@@ -0,0 +1,236 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{
+    BlockedAddressesResponse, ExecuteMsg, FinishMsg, ListResponse, QueryMsg, SwapMsg,
+};
+use crate::state::SwapType;
+use crate::ContractError;
+
+// Blocked addresses cannot create or take part in swaps,
+// and anyone can prune swaps created by blocked addresses
+#[test]
+fn test_address_blocklist() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+    // random prunes swaps
+    let random = Addr::unchecked("random");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates two swaps
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("secondswap".to_string()),
+                ..creation_msg.clone()
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // Only the admin can block addresses
+    let block_buyer_msg = ExecuteMsg::BlockAddress {
+        address: arch_owner.to_string(),
+    };
+    let err = app
+        .execute_contract(random.clone(), swap_inst.clone(), &block_buyer_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(swap_admin.clone(), swap_inst.clone(), &block_buyer_msg, &[])
+        .unwrap();
+
+    // Blocked buyer cannot finish a swap
+    let payment = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1000000000000000000_u128),
+    }];
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg.clone()),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AddressBlocked {
+            address: arch_owner.to_string(),
+        }
+    );
+
+    // swap_admin unblocks the buyer and blocks the seller
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::UnblockAddress {
+                address: arch_owner.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::BlockAddress {
+                address: cw721_owner.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let blocked_query: BlockedAddressesResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::BlockedAddresses {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(blocked_query.addresses, vec![cw721_owner.clone()]);
+
+    // Blocked seller cannot create swaps, and their swaps cannot be finished
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("thirdswap".to_string()),
+                ..creation_msg
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AddressBlocked {
+            address: cw721_owner.to_string(),
+        }
+    );
+    let err = app
+        .execute_contract(
+            arch_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AddressBlocked {
+            address: cw721_owner.to_string(),
+        }
+    );
+
+    // Anyone can prune swaps created by blocked addresses
+    let res = app
+        .execute_contract(
+            random,
+            swap_inst.clone(),
+            &ExecuteMsg::PruneBlocked {
+                ids: vec![
+                    "firstswap".to_string(),
+                    "secondswap".to_string(),
+                    "missingswap".to_string(),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+    let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "pruned" && attr.value == "2"));
+
+    let list_query: ListResponse = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(list_query.swaps.is_empty());
+}
@@ -5,7 +5,9 @@ use cw_multi_test::Executor;
 use cw20::{Cw20ExecuteMsg, Expiration};
 use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
 
-use crate::integration_tests::util::{create_cw20, create_cw721, create_swap, mock_app, query};
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query,
+};
 use crate::msg::{CancelMsg, ExecuteMsg, QueryMsg, SwapMsg};
 use crate::query::PageResult;
 use crate::state::SwapType;
@@ -44,10 +46,11 @@ fn test_cancel_sales() {
     // Create a SwapMsg for creating / finishing a swap
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
         swap_type: SwapType::Sale,
     };
@@ -102,7 +105,7 @@ fn test_cancel_sales() {
         &mut app,
         swap_inst,
         QueryMsg::ListingsOfToken {
-            token_id: token_id,
+            token_id,
             swap_type: Some(SwapType::Sale),
             page: None,
             limit: None,
@@ -141,6 +144,7 @@ fn test_cancel_offers() {
         "wARCH".to_string(),
         Uint128::from(9000000000000000000_u128), // 9 wARCH
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -169,10 +173,11 @@ fn test_cancel_offers() {
     // Bidding buyer creates an offer
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         payment_token: Some(Addr::unchecked(cw20)),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(1000000000000000000_u128), // 1 wARCH
         swap_type: SwapType::Offer,
     };
@@ -217,7 +222,7 @@ fn test_cancel_offers() {
         &mut app,
         swap_inst,
         QueryMsg::ListingsOfToken {
-            token_id: token_id,
+            token_id,
             swap_type: Some(SwapType::Offer),
             page: None,
             limit: None,
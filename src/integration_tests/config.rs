@@ -0,0 +1,294 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    bank_query, create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{ExecuteMsg, FinishMsg, QueryMsg, SwapMsg};
+use crate::state::{Config, SwapType};
+use crate::ContractError;
+
+// Config can only be updated by the admin, one validated setting at a time
+#[test]
+fn test_config_updates() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // treasury receives marketplace fees
+    let treasury = Addr::unchecked("treasury");
+
+    // cw721_owner creates the cw721s
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let other_nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap_inst = create_swap(&mut app, &swap_admin, nft.clone());
+
+    let config_query: Config = query(&mut app, swap_inst.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(
+        config_query,
+        Config {
+            admin: Some(swap_admin.clone()),
+            cw721: nft.clone(),
+            denom: DENOM.to_string(),
+            fee_bps: 0,
+            treasury: None,
+            update_delay: 0,
+        }
+    );
+
+    // Only the admin can update config
+    let err = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 250 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Collection must be a cw721
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetCollection {
+                cw721: swap_inst.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidCollection {
+            address: swap_inst.to_string(),
+        }
+    );
+    let res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetCollection {
+                cw721: other_nft.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "old_cw721" && attr.value == nft));
+    assert!(wasm_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "new_cw721" && attr.value == other_nft));
+
+    // Fees cannot exceed 10%
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 1001 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidFee {
+            fee_bps: 1001,
+            max_fee_bps: 1000,
+        }
+    );
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 250 },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetTreasury {
+                treasury: treasury.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Denom must be a valid native denom
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetDenom {
+                denom: "a".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidDenom {
+            denom: "a".to_string(),
+        }
+    );
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetDenom {
+                denom: "aconst".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let config_query: Config = query(&mut app, swap_inst, QueryMsg::Config {}).unwrap();
+    assert_eq!(
+        config_query,
+        Config {
+            admin: Some(swap_admin),
+            cw721: other_nft,
+            denom: "aconst".to_string(),
+            fee_bps: 250,
+            treasury: Some(treasury),
+            update_delay: 0,
+        }
+    );
+}
+
+// Marketplace fees are deducted from the seller's proceeds
+// and sent to the treasury
+#[test]
+fn test_sale_fees() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+    // treasury receives marketplace fees
+    let treasury = Addr::unchecked("treasury");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract, with a 2.5% fee
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetTreasury {
+                treasury: treasury.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 250 },
+            &[],
+        )
+        .unwrap();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates a swap
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Buyer purchases cw721, paying 1 ARCH and consuming the swap
+    let _res = app
+        .execute_contract(
+            arch_owner,
+            swap_inst,
+            &ExecuteMsg::Finish(finish_msg),
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128),
+            }],
+        )
+        .unwrap();
+
+    // Seller receives 97.5%, treasury receives 2.5%
+    let seller_balance: Coin = bank_query(&app, &cw721_owner);
+    let treasury_balance: Coin = bank_query(&app, &treasury);
+    assert_eq!(
+        seller_balance.amount,
+        Uint128::from(975000000000000000_u128)
+    );
+    assert_eq!(
+        treasury_balance.amount,
+        Uint128::from(25000000000000000_u128)
+    );
+}
@@ -0,0 +1,105 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, StdError, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{create_cw721, create_swap, mock_app, query};
+use crate::msg::{DetailsManyResponse, DetailsResponse, ExecuteMsg, QueryMsg, SwapMsg};
+use crate::state::SwapType;
+
+// Details of many swaps are returned in the order requested,
+// along with their ids
+#[test]
+fn test_details_many() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap_inst = create_swap(&mut app, &swap_admin, nft.clone());
+
+    // cw721_owner mints and lists two cw721s
+    for (token_id, price) in [("petrify", 1000_u32), ("ossify", 2000_u32)] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: cw721_owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+        let creation_msg = SwapMsg {
+            id: Some(format!("{}swap", token_id)),
+            payment_token: None,
+            denom: None,
+            token_id: token_id.to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(price),
+            swap_type: SwapType::Sale,
+        };
+        let _res = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(creation_msg),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Details include the swap id
+    let details_query: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: "petrifyswap".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(details_query.id, "petrifyswap");
+
+    let details_query: DetailsManyResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::DetailsMany {
+            ids: vec![
+                "ossifyswap".to_string(),
+                "missingswap".to_string(),
+                "petrifyswap".to_string(),
+            ],
+        },
+    )
+    .unwrap();
+    let swaps: Vec<(String, Option<Uint128>)> = details_query
+        .swaps
+        .into_iter()
+        .map(|entry| (entry.id, entry.details.map(|details| details.price)))
+        .collect();
+    assert_eq!(
+        swaps,
+        vec![
+            ("ossifyswap".to_string(), Some(Uint128::from(2000_u32))),
+            ("missingswap".to_string(), None),
+            ("petrifyswap".to_string(), Some(Uint128::from(1000_u32))),
+        ]
+    );
+
+    // At most 100 ids per query
+    let details_query: Result<DetailsManyResponse, StdError> = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::DetailsMany {
+            ids: (0..101).map(|id| id.to_string()).collect(),
+        },
+    );
+    assert!(details_query.is_err());
+}
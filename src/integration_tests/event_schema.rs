@@ -0,0 +1,205 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Event, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{create_cw721, create_swap, mint_native, mock_app, DENOM};
+use crate::msg::{CancelMsg, ExecuteMsg, FinishMsg, SwapMsg, UpdateMsg};
+use crate::state::SwapType;
+
+fn find_event<'a>(res: &'a AppResponse, ty: &str) -> &'a Event {
+    res.events.iter().find(|e| e.ty == ty).unwrap()
+}
+
+fn attr<'a>(event: &'a Event, key: &str) -> &'a str {
+    &event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .unwrap()
+        .value
+}
+
+// Swap state changes emit typed events with full swap details
+#[test]
+fn test_event_schema() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+    // treasury receives marketplace fees
+    let treasury = Addr::unchecked("treasury");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract, with a 2.5% fee
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetTreasury {
+                treasury: treasury.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 250 },
+            &[],
+        )
+        .unwrap();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(2000000000000000000_u128), // 2 ARCH as aarch
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates three swaps
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id: token_id.clone(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap();
+    let create_event = find_event(&res, "wasm-archid_market_create");
+    assert_eq!(attr(create_event, "swap_id"), "firstswap");
+    assert_eq!(attr(create_event, "creator"), cw721_owner.as_str());
+    assert_eq!(attr(create_event, "nft_contract"), nft.as_str());
+    assert_eq!(attr(create_event, "token_id"), token_id);
+    assert_eq!(attr(create_event, "swap_type"), "sale");
+    assert_eq!(attr(create_event, "payment_type"), "native");
+    assert_eq!(attr(create_event, "payment_token"), DENOM);
+    assert_eq!(attr(create_event, "price"), "1000000000000000000");
+    assert_eq!(
+        attr(create_event, "expires"),
+        "expiration height: 384798573487439743"
+    );
+    for id in ["secondswap", "thirdswap"] {
+        let _res = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(SwapMsg {
+                    id: Some(id.to_string()),
+                    ..creation_msg.clone()
+                }),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Update reports the new and previous terms
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: "firstswap".to_string(),
+                expires: Expiration::AtHeight(384798573487439744),
+                price: Uint128::from(2000000000000000000_u128),
+            }),
+            &[],
+        )
+        .unwrap();
+    let update_event = find_event(&res, "wasm-archid_market_update");
+    assert_eq!(attr(update_event, "price"), "2000000000000000000");
+    assert_eq!(attr(update_event, "old_price"), "1000000000000000000");
+    assert_eq!(
+        attr(update_event, "old_expires"),
+        "expiration height: 384798573487439743"
+    );
+
+    // Cancel reports the cancelled swap
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Cancel(CancelMsg {
+                id: "thirdswap".to_string(),
+            }),
+            &[],
+        )
+        .unwrap();
+    let cancel_event = find_event(&res, "wasm-archid_market_cancel");
+    assert_eq!(attr(cancel_event, "swap_id"), "thirdswap");
+    assert_eq!(attr(cancel_event, "token_id"), token_id);
+
+    // Finish reports the parties, fees and refunds
+    let res = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst,
+            &ExecuteMsg::Finish(FinishMsg {
+                id: "firstswap".to_string(),
+                expected_price: Uint128::from(2000000000000000000_u128),
+                expected_payment_token: DENOM.to_string(),
+                expected_revision: None,
+            }),
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(2000000000000000000_u128),
+            }],
+        )
+        .unwrap();
+    let finish_event = find_event(&res, "wasm-archid_market_finish");
+    assert_eq!(attr(finish_event, "swap_id"), "firstswap");
+    assert_eq!(attr(finish_event, "price"), "2000000000000000000");
+    assert_eq!(attr(finish_event, "seller"), cw721_owner.as_str());
+    assert_eq!(attr(finish_event, "buyer"), arch_owner.as_str());
+    assert_eq!(attr(finish_event, "fee"), "50000000000000000");
+    assert_eq!(attr(finish_event, "treasury"), treasury.as_str());
+    assert_eq!(attr(finish_event, "seller_amount"), "1950000000000000000");
+    assert_eq!(attr(finish_event, "refund"), "0");
+
+    // Other swaps of the sold token are invalidated
+    let invalidate_event = find_event(&res, "wasm-archid_market_invalidate");
+    assert_eq!(attr(invalidate_event, "swap_id"), "secondswap");
+    assert_eq!(attr(invalidate_event, "sold_swap_id"), "firstswap");
+}
@@ -0,0 +1,339 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{DetailsResponse, ExecuteMsg, FinishMsg, QueryMsg, SwapMsg, UpdateMsg};
+use crate::state::SwapType;
+use crate::utils::MAX_UPDATE_DELAY;
+use crate::ContractError;
+
+// Finish settles from the stored swap, and fails
+// if its terms no longer match the buyer's expectations
+#[test]
+fn test_finish_expectations() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(2000000000000000000_u128), // 2 ARCH as aarch
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates a swap for 1 ARCH
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Seller raises the price before the buyer's Finish lands
+    let _res = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: "firstswap".to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(2000000000000000000_u128), // 2 ARCH as aarch
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // Buyer expected to pay at most 1 ARCH
+    let payment = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(2000000000000000000_u128),
+    }];
+    let finish_msg = FinishMsg {
+        id: "firstswap".to_string(),
+        expected_price: Uint128::from(1000000000000000000_u128),
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg.clone()),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnexpectedPrice {
+            price: Uint128::from(2000000000000000000_u128),
+            expected_price: Uint128::from(1000000000000000000_u128),
+        }
+    );
+
+    // Buyer expected to pay in another denom
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(FinishMsg {
+                expected_price: Uint128::from(3000000000000000000_u128),
+                expected_payment_token: "uatom".to_string(),
+                expected_revision: None,
+                ..finish_msg.clone()
+            }),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnexpectedPaymentToken {
+            payment_token: DENOM.to_string(),
+            expected: "uatom".to_string(),
+        }
+    );
+
+    // Buyer accepts paying up to 3 ARCH, and pays the stored price of 2 ARCH
+    let _res = app
+        .execute_contract(
+            arch_owner,
+            swap_inst,
+            &ExecuteMsg::Finish(FinishMsg {
+                expected_price: Uint128::from(3000000000000000000_u128),
+                ..finish_msg
+            }),
+            &payment,
+        )
+        .unwrap();
+}
+
+// Updates bump the swap revision, which Finish can pin;
+// with an update delay, updated swaps cannot be taken right away
+#[test]
+fn test_update_revision_and_delay() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates a swap
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+
+    let details_query: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: "firstswap".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(details_query.revision, 0);
+
+    // Update delays are capped
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetUpdateDelay { blocks: u64::MAX },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidUpdateDelay {
+            blocks: u64::MAX,
+            max_blocks: MAX_UPDATE_DELAY,
+        }
+    );
+
+    // swap_admin sets an update delay of 5 blocks
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::SetUpdateDelay { blocks: 5 },
+            &[],
+        )
+        .unwrap();
+
+    // Seller updates the swap, keeping the same price
+    let update_msg = UpdateMsg {
+        id: "firstswap".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128),
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Update(update_msg),
+            &[],
+        )
+        .unwrap();
+    let updated_at = app.block_info().height;
+
+    // Buyer cannot finish until the delay has passed
+    let payment = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1000000000000000000_u128),
+    }];
+    let finish_msg = FinishMsg {
+        id: "firstswap".to_string(),
+        expected_price: Uint128::from(1000000000000000000_u128),
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: Some(0),
+    };
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg.clone()),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UpdatePending {
+            height: updated_at + 5,
+        }
+    );
+    app.update_block(|block| block.height += 5);
+
+    // Buyer saw revision 0, but the swap is now at revision 1
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg.clone()),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnexpectedRevision {
+            revision: 1,
+            expected_revision: 0,
+        }
+    );
+
+    let _res = app
+        .execute_contract(
+            arch_owner,
+            swap_inst,
+            &ExecuteMsg::Finish(FinishMsg {
+                expected_revision: Some(1),
+                ..finish_msg
+            }),
+            &payment,
+        )
+        .unwrap();
+}
@@ -8,12 +8,13 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::contract::DENOM;
 use crate::integration_tests::util::{
-    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query,
+    add_payment_token, bank_query, create_cw20, create_cw721, create_swap, mint_denom, mint_native,
+    mock_app, query, DENOM,
 };
-use crate::msg::{ExecuteMsg, SwapMsg};
+use crate::msg::{ExecuteMsg, FinishMsg, SwapMsg};
 use crate::state::SwapType;
+use crate::ContractError;
 
 // cw721 buyer must send correct ARCH amount
 #[test]
@@ -40,6 +41,12 @@ fn test_invalid_payment_native() {
         arch_owner.to_string(),
         Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
     );
+    mint_denom(
+        &mut app,
+        arch_owner.to_string(),
+        "uatom",
+        Uint128::from(5000000000000000000_u128),
+    );
 
     // cw721_owner mints a cw721
     let token_id = "petrify".to_string();
@@ -56,14 +63,20 @@ fn test_invalid_payment_native() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(5000000000000000000_u128), // 5 ARCH as aarch
         swap_type: SwapType::Sale,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
     let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
@@ -85,36 +98,69 @@ fn test_invalid_payment_native() {
         )
         .unwrap();
 
+    // Buyer attempts to purchase cw721, paying in the wrong denom
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg.clone()),
+            &[Coin {
+                denom: String::from("uatom"),
+                amount: Uint128::from(5000000000000000000_u128),
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WrongDenom {
+            expected: DENOM.to_string(),
+            sent: "uatom".to_string(),
+        }
+    );
+
     // Buyer attempts to purchase cw721, under paying 1 ARCH
-    assert!(app
+    let err = app
         .execute_contract(
             arch_owner.clone(),
             swap_inst.clone(),
             &ExecuteMsg::Finish(finish_msg),
             &[Coin {
                 denom: String::from(DENOM),
-                amount: Uint128::from(1000000000000000000_u128)
-            }]
+                amount: Uint128::from(1000000000000000000_u128),
+            }],
         )
-        .is_err());
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InsufficientFunds {
+            expected: Coin {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(5000000000000000000_u128),
+            },
+            sent: Coin {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(1000000000000000000_u128),
+            },
+        }
+    );
 
     // cw721_owner has retained the NFT
     let owner_query: OwnerOfResponse = query(
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
     .unwrap();
 
     // cw721_owner has not received the ARCH amount
-    let cw721_owner_balance: Coin = bank_query(&mut app, &cw721_owner);
+    let cw721_owner_balance: Coin = bank_query(&app, &cw721_owner);
     // dbg!(cw721_owner_balance.amount);
 
     // arch_owner has retained their original balance (minus gas fees)
-    let arch_owner_balance: Coin = bank_query(&mut app, &cw721_owner);
+    let arch_owner_balance: Coin = bank_query(&app, &cw721_owner);
     // dbg!(arch_owner_balance.amount);
 
     assert_eq!(cw721_owner_balance.amount.u128(), 0);
@@ -164,33 +210,41 @@ fn test_invalid_payment_native_offer() {
     // Bidding buyer creates an offer (with an invalid payment)
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(9000000000000000000_u128), // 9 ARCH as aarch
         swap_type: SwapType::Offer,
     };
 
     // Invalid payment must err
-    assert!(app
+    let err = app
         .execute_contract(
             arch_owner.clone(),
             swap_inst.clone(),
             &ExecuteMsg::Create(creation_msg),
             &[Coin {
                 denom: String::from(DENOM),
-                amount: Uint128::from(8000000000000000000_u128)
-            }]
+                amount: Uint128::from(8000000000000000000_u128),
+            }],
         )
-        .is_err());
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnsupportedSwapType {
+            swap_type: SwapType::Offer,
+            denom: DENOM.to_string(),
+        }
+    );
 
     // cw721_owner has not transferred the NFT
     let owner_query: OwnerOfResponse = query(
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
@@ -198,7 +252,7 @@ fn test_invalid_payment_native_offer() {
     assert_eq!(owner_query.owner, cw721_owner);
 
     // Bidding buyer's account has not been debited
-    let arch_owner_balance: Coin = bank_query(&mut app, &arch_owner);
+    let arch_owner_balance: Coin = bank_query(&app, &arch_owner);
     assert_eq!(
         arch_owner_balance.amount,
         Uint128::from(10000000000000000000_u128)
@@ -235,6 +289,7 @@ fn test_invalid_payment_cw20() {
         "tscw".to_string(),
         Uint128::from(100000_u32),
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
 
     // cw721_owner mints a cw721
     let token_id = "petrify".to_string();
@@ -251,14 +306,20 @@ fn test_invalid_payment_cw20() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: Some(Addr::unchecked(cw20.clone())),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(100000_u32),
         swap_type: SwapType::Offer,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: creation_msg.payment_token.as_ref().unwrap().to_string(),
+        expected_revision: None,
+    };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
     let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
@@ -340,6 +401,7 @@ fn test_invalid_payment_cw20_offer() {
         "tscw".to_string(),
         Uint128::from(100000_u32),
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -367,14 +429,20 @@ fn test_invalid_payment_cw20_offer() {
 
     // Bidding buyer (cw20_owner) creates an offer
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: Some(Addr::unchecked(cw20)),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(100000_u32),
         swap_type: SwapType::Offer,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: creation_msg.payment_token.as_ref().unwrap().to_string(),
+        expected_revision: None,
+    };
 
     let _res = app
         .execute_contract(
@@ -396,21 +464,29 @@ fn test_invalid_payment_cw20_offer() {
         .unwrap();
 
     // cw721_owner accepts the cw20 buyer's offer but the swap must fail (invalid payment)
-    assert!(app
+    let err = app
         .execute_contract(
             cw721_owner.clone(),
             swap_inst.clone(),
             &ExecuteMsg::Finish(finish_msg),
-            &[]
+            &[],
         )
-        .is_err());
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InsufficientAllowance {
+            token: cw20_inst.to_string(),
+            expected: Uint128::from(100000_u32),
+            allowance: Uint128::from(10000_u32),
+        }
+    );
 
     // cw721_owner has not transferred the NFT
     let owner_query: OwnerOfResponse = query(
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
@@ -1,9 +1,28 @@
 pub mod util;
 
+mod admin;
+mod best_prices;
+mod blocklist;
 mod cancel;
+mod config;
+mod details;
+mod event_schema;
+mod finish_expectations;
 mod invalid_payment;
+mod moderation;
+mod native_denom;
 mod offer;
+mod offers_received;
 mod overpayment;
 mod pagination;
+mod pause;
+mod payment_token;
+mod roles;
 mod sale;
+mod search;
+mod stats;
+mod swap_id;
+mod swap_totals;
+mod trade_history;
 mod update;
+mod user_activity;
@@ -0,0 +1,211 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{create_cw721, create_swap, mock_app, query};
+use crate::msg::{
+    BlockedNamesResponse, BlockedPatternsResponse, DetailsResponse, ExecuteMsg, QueryMsg, SwapMsg,
+};
+use crate::state::{Role, SwapType};
+use crate::ContractError;
+
+// Moderators can force-remove swaps and block names from being listed
+#[test]
+fn test_moderation() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // moderator can delist swaps and block names
+    let moderator = Addr::unchecked("moderator");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract, and grants the moderator role
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::Moderator,
+                address: moderator.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // cw721_owner mints two cw721s, and approves the swap contract to spend them
+    for token_id in ["petrify", "paypal-support"] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: cw721_owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+    }
+    let nft_approve_msg = Cw721ExecuteMsg::ApproveAll::<Extension> {
+        operator: swap.to_string(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates a swap
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id: "petrify".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap();
+
+    // Only the admin or a moderator can delist
+    let delist_msg = ExecuteMsg::Delist {
+        id: "firstswap".to_string(),
+        reason: "impersonation".to_string(),
+    };
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap_inst.clone(), &delist_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let res = app
+        .execute_contract(moderator.clone(), swap_inst.clone(), &delist_msg, &[])
+        .unwrap();
+    let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm_event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "reason" && attr.value == "impersonation"));
+
+    // Swap has been removed
+    let details_query: Result<DetailsResponse, _> = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: "firstswap".to_string(),
+        },
+    );
+    assert!(details_query.is_err());
+
+    // moderator blocks a name and a pattern
+    let _res = app
+        .execute_contract(
+            moderator.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::BlockName {
+                token_id: "petrify".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            moderator.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::BlockPattern {
+                pattern: "PayPal".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let names_query: BlockedNamesResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::BlockedNames {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(names_query.names, vec!["petrify".to_string()]);
+    let patterns_query: BlockedPatternsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::BlockedPatterns {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(patterns_query.patterns, vec!["paypal".to_string()]);
+
+    // Blocked names cannot be listed
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NameBlocked {
+            token_id: "petrify".to_string(),
+        }
+    );
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("secondswap".to_string()),
+                token_id: "paypal-support".to_string(),
+                ..creation_msg.clone()
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NameBlocked {
+            token_id: "paypal-support".to_string(),
+        }
+    );
+
+    // moderator unblocks the name, it can be listed again
+    let _res = app
+        .execute_contract(
+            moderator,
+            swap_inst.clone(),
+            &ExecuteMsg::UnblockName {
+                token_id: "petrify".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst,
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+}
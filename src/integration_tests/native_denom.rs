@@ -0,0 +1,198 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721::OwnerOfResponse;
+use cw721_base::{
+    msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
+};
+
+use crate::integration_tests::util::{
+    bank_query_denom, create_cw721, create_swap, mint_denom, mock_app, query,
+};
+use crate::msg::{ExecuteMsg, FinishMsg, NativeDenomsResponse, QueryMsg, SwapMsg};
+use crate::query::PageResult;
+use crate::state::SwapType;
+use crate::ContractError;
+
+static USDC: &str = "ibc/D189335C6E4A68B513C10AB227BF1C1D38C746766278BA3EEB4FB14124F1D858";
+
+// Swap buyer pays with an allowlisted IBC denom
+#[test]
+fn test_buy_allowlisted_denom() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // usdc_owner owns USDC
+    let usdc_owner = Addr::unchecked("usdc_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint USDC to `usdc_owner`
+    mint_denom(
+        &mut app,
+        usdc_owner.to_string(),
+        USDC,
+        Uint128::from(10000000_u128), // 10 USDC
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+
+    // Create a SwapMsg for creating / finishing a swap
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: Some(USDC.to_string()),
+        token_id: token_id.clone(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(5000000_u128), // 5 USDC
+        swap_type: SwapType::Sale,
+    };
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: USDC.to_string(),
+        expected_revision: None,
+    };
+
+    // USDC is not accepted until allowlisted
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DenomNotAllowed {
+            denom: USDC.to_string(),
+        }
+    );
+
+    // Only the admin can allowlist a denom
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::AddNativeDenom {
+                denom: USDC.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // swap_admin allowlists USDC
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::AddNativeDenom {
+                denom: USDC.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let denoms_query: NativeDenomsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::NativeDenoms {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(denoms_query.denoms, vec![USDC.to_string()]);
+
+    // cw721 seller (cw721_owner) creates a swap
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Swap is found by its native denom
+    let swap_query: PageResult = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::SwapsByDenom {
+            payment_token: None,
+            denom: Some(USDC.to_string()),
+            swap_type: Some(SwapType::Sale),
+            page: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(swap_query.total, 1);
+    assert_eq!(swap_query.swaps[0].swap.denom, Some(USDC.to_string()));
+
+    // Buyer purchases cw721, paying 5 USDC and consuming the swap
+    let _res = app
+        .execute_contract(
+            usdc_owner.clone(),
+            swap_inst,
+            &ExecuteMsg::Finish(finish_msg),
+            &[Coin {
+                denom: USDC.to_string(),
+                amount: Uint128::from(5000000_u128),
+            }],
+        )
+        .unwrap();
+
+    // usdc_owner has received the NFT
+    let owner_query: OwnerOfResponse = query(
+        &mut app,
+        nft,
+        Cw721QueryMsg::OwnerOf {
+            token_id,
+            include_expired: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(owner_query.owner, usdc_owner);
+
+    // cw721_owner has received the USDC amount
+    let seller_balance: Coin = bank_query_denom(&app, &cw721_owner, USDC);
+    assert_eq!(seller_balance.amount, Uint128::from(5000000_u128));
+    let buyer_balance: Coin = bank_query_denom(&app, &usdc_owner, USDC);
+    assert_eq!(buyer_balance.amount, Uint128::from(5000000_u128));
+}
@@ -8,9 +8,12 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::integration_tests::util::{create_cw20, create_cw721, create_swap, mock_app, query};
-use crate::msg::{ExecuteMsg, SwapMsg};
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query,
+};
+use crate::msg::{ExecuteMsg, FinishMsg, SwapMsg};
 use crate::state::SwapType;
+use crate::ContractError;
 
 // cw721_owner accepts an offer for some cw20 from cw20_owner
 // XXX: cw20 spending approvals will only work for one swap at a time
@@ -43,6 +46,7 @@ fn test_cw20_offer_accepted() {
         "tscw".to_string(),
         Uint128::from(100000_u32),
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -75,14 +79,20 @@ fn test_cw20_offer_accepted() {
 
     // Bidding buyer (cw20_owner) creates an offer
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: Some(Addr::unchecked(cw20)),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(100000_u32),
         swap_type: SwapType::Offer,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: creation_msg.payment_token.as_ref().unwrap().to_string(),
+        expected_revision: None,
+    };
 
     let _res = app
         .execute_contract(
@@ -118,7 +128,7 @@ fn test_cw20_offer_accepted() {
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
@@ -165,6 +175,7 @@ fn test_cw20_offer_cannot_be_accepted_by_someone_else() {
         "tscw".to_string(),
         Uint128::from(100000_u32),
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -197,14 +208,20 @@ fn test_cw20_offer_cannot_be_accepted_by_someone_else() {
 
     // Bidding buyer (cw20_owner) creates an offer
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: Some(Addr::unchecked(cw20)),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(100000_u32),
         swap_type: SwapType::Offer,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: creation_msg.payment_token.as_ref().unwrap().to_string(),
+        expected_revision: None,
+    };
 
     let _res = app
         .execute_contract(
@@ -226,12 +243,20 @@ fn test_cw20_offer_cannot_be_accepted_by_someone_else() {
         .unwrap();
 
     // bad actor accepts the cw20 buyer's offer
-    let res = app.execute_contract(
-        bad_actor.clone(),
-        swap_inst.clone(),
-        &ExecuteMsg::Finish(finish_msg),
-        &[],
-    );
+    let err = app
+        .execute_contract(
+            bad_actor.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg),
+            &[],
+        )
+        .unwrap_err();
 
-    assert!(res.is_err());
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotTokenOwner {
+            sender: bad_actor.to_string(),
+            token_id,
+        }
+    );
 }
@@ -0,0 +1,179 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query,
+};
+use crate::msg::{ExecuteMsg, OffersReceivedResponse, QueryMsg, SwapMsg};
+use crate::state::SwapType;
+
+// Owners see the active offers on all tokens they hold,
+// grouped by payment token, highest price first
+#[test]
+fn test_offers_received() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // other_owner owns another cw721
+    let other_owner = Addr::unchecked("other_owner");
+    // cw20_owner owns the cw20
+    let cw20_owner = Addr::unchecked("cw20_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw20_owner creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(100000_u32),
+    );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
+    let other_cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "othercw".to_string(),
+        "ocw".to_string(),
+        Uint128::from(100000_u32),
+    );
+    add_payment_token(&mut app, &swap_admin, &swap, &other_cw20);
+
+    // cw721_owner mints two cw721s, and one for other_owner
+    for (token_id, owner) in [
+        ("petrify", &cw721_owner),
+        ("ossify", &cw721_owner),
+        ("calcify", &other_owner),
+    ] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+    }
+
+    // cw20_owner makes offers on all three tokens;
+    // the "ossifylow" offer expires at the next block
+    let height = app.block_info().height;
+    for (id, token_id, price, expires) in [
+        ("petrifylow", "petrify", 100_u32, Expiration::Never {}),
+        ("ossifyhigh", "ossify", 300_u32, Expiration::Never {}),
+        ("petrifyhigh", "petrify", 200_u32, Expiration::Never {}),
+        (
+            "ossifylow",
+            "ossify",
+            50_u32,
+            Expiration::AtHeight(height + 1),
+        ),
+        ("calcifyhigh", "calcify", 1000_u32, Expiration::Never {}),
+    ] {
+        let offer_msg = SwapMsg {
+            id: Some(id.to_string()),
+            payment_token: Some(cw20.clone()),
+            denom: None,
+            token_id: token_id.to_string(),
+            expires,
+            price: Uint128::from(price),
+            swap_type: SwapType::Offer,
+        };
+        let _res = app
+            .execute_contract(
+                cw20_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(offer_msg),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // A larger amount of another cw20 is ranked separately
+    let _res = app
+        .execute_contract(
+            cw20_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("petrifyother".to_string()),
+                payment_token: Some(other_cw20.clone()),
+                denom: None,
+                token_id: "petrify".to_string(),
+                expires: Expiration::Never {},
+                price: Uint128::from(5000_u32),
+                swap_type: SwapType::Offer,
+            }),
+            &[],
+        )
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    let offers_query: OffersReceivedResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::OffersReceived {
+            owner: cw721_owner.to_string(),
+            payment_token: None,
+            page: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let offers: Vec<(String, Uint128)> = offers_query
+        .offers
+        .into_iter()
+        .map(|entry| (entry.id, entry.swap.price))
+        .collect();
+    assert_eq!(
+        offers,
+        vec![
+            ("ossifyhigh".to_string(), Uint128::from(300_u32)),
+            ("petrifyhigh".to_string(), Uint128::from(200_u32)),
+            ("petrifylow".to_string(), Uint128::from(100_u32)),
+            ("petrifyother".to_string(), Uint128::from(5000_u32)),
+        ]
+    );
+    assert_eq!(offers_query.total, 4);
+
+    // Offers in a single payment token
+    let offers_query: OffersReceivedResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::OffersReceived {
+            owner: cw721_owner.to_string(),
+            payment_token: Some(other_cw20.to_string()),
+            page: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(offers_query.offers.len(), 1);
+    assert_eq!(offers_query.offers[0].id, "petrifyother");
+
+    let offers_query: OffersReceivedResponse = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::OffersReceived {
+            owner: other_owner.to_string(),
+            payment_token: None,
+            page: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(offers_query.offers.len(), 1);
+    assert_eq!(offers_query.offers[0].id, "calcifyhigh");
+}
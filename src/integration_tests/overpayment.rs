@@ -8,15 +8,16 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::contract::DENOM;
 use crate::integration_tests::util::{
-    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query,
+    add_payment_token, bank_query, bank_query_denom, create_cw20, create_cw721, create_swap,
+    mint_denom, mint_native, mock_app, query, DENOM,
 };
-use crate::msg::{ExecuteMsg, SwapMsg};
+use crate::msg::{ExecuteMsg, FinishMsg, SwapMsg};
 use crate::state::SwapType;
 
 // cw721 buyer (arch_owner) overpays
-// seller (cw721_owner) receives the full overpaid amount
+// seller (cw721_owner) receives the swap price and
+// the buyer is refunded the excess (and any unrelated denoms)
 #[test]
 fn test_overpayment_native() {
     let mut app = mock_app();
@@ -41,6 +42,12 @@ fn test_overpayment_native() {
         arch_owner.to_string(),
         Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
     );
+    mint_denom(
+        &mut app,
+        arch_owner.to_string(),
+        "uatom",
+        Uint128::from(1000000_u128),
+    );
 
     // cw721_owner mints a cw721
     let token_id = "petrify".to_string();
@@ -57,14 +64,20 @@ fn test_overpayment_native() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
         swap_type: SwapType::Sale,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
     let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
@@ -86,38 +99,59 @@ fn test_overpayment_native() {
         )
         .unwrap();
 
-    // Buyer purchases cw721, paying 10 ARCH and consuming the swap
-    let _res = app
+    // Buyer purchases cw721, paying 10 ARCH (and some uatom) and consuming the swap
+    let res = app
         .execute_contract(
             arch_owner.clone(),
             swap_inst.clone(),
             &ExecuteMsg::Finish(finish_msg),
-            &[Coin {
-                denom: String::from(DENOM),
-                amount: Uint128::from(10000000000000000000_u128),
-            }],
+            &[
+                Coin {
+                    denom: String::from(DENOM),
+                    amount: Uint128::from(10000000000000000000_u128),
+                },
+                Coin {
+                    denom: String::from("uatom"),
+                    amount: Uint128::from(1000000_u128),
+                },
+            ],
         )
         .unwrap();
 
+    // Response states the refunded amount
+    let refund_attr = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "refund")
+        .unwrap();
+    assert_eq!(refund_attr.value, "9000000000000000000aarch,1000000uatom");
+
     // arch_owner has received the NFT
     let owner_query: OwnerOfResponse = query(
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
     .unwrap();
 
     // cw721_owner has received the ARCH amount
-    let balance_query: Coin = bank_query(&mut app, &cw721_owner);
+    let balance_query: Coin = bank_query(&app, &cw721_owner);
+
+    // arch_owner has been refunded the overpaid ARCH and the uatom
+    let refund_query: Coin = bank_query(&app, &arch_owner);
+    let denom_refund_query: Coin = bank_query_denom(&app, &arch_owner, "uatom");
 
     assert_eq!(owner_query.owner, arch_owner);
     assert_eq!(
         balance_query.amount,
-        Uint128::from(10000000000000000000_u128)
+        Uint128::from(1000000000000000000_u128)
     );
+    assert_eq!(refund_query.amount, Uint128::from(9000000000000000000_u128));
+    assert_eq!(denom_refund_query.amount, Uint128::from(1000000_u128));
 }
 
 // Over paying will fail, must send exactly the required funds
@@ -163,10 +197,11 @@ fn test_overpayment_native_offer() {
     // Bidding buyer creates an offer
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(5000000000000000000_u128), // 5 ARCH as aarch
         swap_type: SwapType::Offer,
     };
@@ -215,6 +250,7 @@ fn test_overpayment_cw20() {
         "tscw".to_string(),
         Uint128::from(1000000_u32),
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -232,14 +268,20 @@ fn test_overpayment_cw20() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: Some(Addr::unchecked(cw20.clone())),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(100000_u32),
         swap_type: SwapType::Offer,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: creation_msg.payment_token.as_ref().unwrap().to_string(),
+        expected_revision: None,
+    };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
     let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
@@ -286,7 +328,7 @@ fn test_overpayment_cw20() {
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
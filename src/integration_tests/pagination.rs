@@ -8,7 +8,7 @@ use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
 use crate::integration_tests::util::{
     create_cw721, create_swap, has_unique_elements, mock_app, query,
 };
-use crate::msg::{ExecuteMsg, ListResponse, QueryMsg, SwapMsg};
+use crate::msg::{DetailsResponse, ExecuteMsg, ListResponse, QueryMsg, SwapMsg};
 use crate::query::PageResult;
 use crate::state::SwapType;
 
@@ -81,10 +81,11 @@ fn test_pagination() {
 
         // Swap msg
         let creation_msg = SwapMsg {
-            id: token_id.clone(),
+            id: Some(token_id.clone()),
             payment_token: None,
+            denom: None,
             token_id: token_id.clone(),
-            expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+            expires: Expiration::AtHeight(384798573487439743),
             price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
             swap_type: SwapType::Sale,
         };
@@ -108,7 +109,7 @@ fn test_pagination() {
         swap_inst.clone(),
         QueryMsg::List {
             start_after: None,
-            limit: Some(limit.clone()),
+            limit: Some(limit),
         },
     )
     .unwrap();
@@ -118,7 +119,7 @@ fn test_pagination() {
         swap_inst.clone(),
         QueryMsg::List {
             start_after: Some(page_1.swaps[4].clone()),
-            limit: Some(limit.clone()),
+            limit: Some(limit),
         },
     )
     .unwrap();
@@ -128,7 +129,7 @@ fn test_pagination() {
         swap_inst.clone(),
         QueryMsg::List {
             start_after: Some(page_2.swaps[4].clone()),
-            limit: Some(limit.clone()),
+            limit: Some(limit),
         },
     )
     .unwrap();
@@ -178,10 +179,21 @@ fn test_pagination() {
     all_res_b.append(&mut page_2b.swaps.clone());
     let mut token_ids_b: Vec<String> = vec![];
     for swap in all_res_b.iter() {
-        token_ids_b.push(swap.clone().token_id);
+        token_ids_b.push(swap.swap.token_id.clone());
     }
     assert!(has_unique_elements(token_ids_b));
 
+    // Paginated results include the id of each swap
+    let swap_details: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: page_2b.swaps[0].id.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(swap_details.token_id, page_2b.swaps[0].swap.token_id);
+
     // Query SwapsOf entry point for 2 pages
     // Page 1
     let page_1c: PageResult = query(
@@ -220,7 +232,7 @@ fn test_pagination() {
     all_res_c.append(&mut page_2c.swaps.clone());
     let mut token_ids_c: Vec<String> = vec![];
     for swap in all_res_c.iter() {
-        token_ids_c.push(swap.clone().token_id);
+        token_ids_c.push(swap.swap.token_id.clone());
     }
     assert!(has_unique_elements(token_ids_c));
 
@@ -264,7 +276,7 @@ fn test_pagination() {
     all_res_d.append(&mut page_2d.swaps.clone());
     let mut token_ids_d: Vec<String> = vec![];
     for swap in all_res_d.iter() {
-        token_ids_d.push(swap.clone().token_id);
+        token_ids_d.push(swap.swap.token_id.clone());
     }
     assert!(has_unique_elements(token_ids_d));
 
@@ -275,6 +287,7 @@ fn test_pagination() {
         swap_inst.clone(),
         QueryMsg::SwapsByDenom {
             payment_token: None,
+            denom: None,
             swap_type: Some(SwapType::Sale),
             page: None,
             limit: None,
@@ -287,6 +300,7 @@ fn test_pagination() {
         swap_inst.clone(),
         QueryMsg::SwapsByDenom {
             payment_token: None,
+            denom: None,
             swap_type: Some(SwapType::Sale),
             page: Some(1_u32),
             limit: None,
@@ -306,7 +320,7 @@ fn test_pagination() {
     all_res_e.append(&mut page_2e.swaps.clone());
     let mut token_ids_e: Vec<String> = vec![];
     for swap in all_res_e.iter() {
-        token_ids_e.push(swap.clone().token_id);
+        token_ids_e.push(swap.swap.token_id.clone());
     }
     assert!(has_unique_elements(token_ids_e));
 
@@ -348,7 +362,7 @@ fn test_pagination() {
     all_res_f.append(&mut page_2f.swaps.clone());
     let mut token_ids_f: Vec<String> = vec![];
     for swap in all_res_f.iter() {
-        token_ids_f.push(swap.clone().token_id);
+        token_ids_f.push(swap.swap.token_id.clone());
     }
     assert!(has_unique_elements(token_ids_f));
 
@@ -0,0 +1,230 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{
+    CancelMsg, ExecuteMsg, FinishMsg, PauseStatusResponse, QueryMsg, SwapMsg, UpdateMsg,
+};
+use crate::state::{Role, SwapType};
+use crate::ContractError;
+
+// While paused, trading is halted but swaps can still be cancelled;
+// pausers can pause, only the admin or a guardian can unpause
+#[test]
+fn test_pause() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // pauser can pause trading
+    let pauser = Addr::unchecked("pauser");
+    // guardian can pause and unpause trading
+    let guardian = Addr::unchecked("guardian");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates two swaps
+    let creation_msg = SwapMsg {
+        id: Some("firstswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("secondswap".to_string()),
+                ..creation_msg.clone()
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // swap_admin grants the pauser and guardian roles
+    for (role, address) in [(Role::Pauser, &pauser), (Role::Guardian, &guardian)] {
+        let _res = app
+            .execute_contract(
+                swap_admin.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::GrantRole {
+                    role,
+                    address: address.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Only the admin or a pauser can pause
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(
+            pauser.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Pause {},
+            &[],
+        )
+        .unwrap();
+
+    let status_query: PauseStatusResponse =
+        query(&mut app, swap_inst.clone(), QueryMsg::PauseStatus {}).unwrap();
+    assert_eq!(status_query, PauseStatusResponse { paused: true });
+
+    // Pausers cannot unpause
+    let err = app
+        .execute_contract(pauser, swap_inst.clone(), &ExecuteMsg::Unpause {}, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Create, Update and Finish are rejected
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("thirdswap".to_string()),
+                ..creation_msg
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Paused {}
+    );
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: "firstswap".to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(2000000000000000000_u128),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Paused {}
+    );
+    let payment = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1000000000000000000_u128),
+    }];
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(finish_msg.clone()),
+            &payment,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Paused {}
+    );
+
+    // Cancel still works
+    let _res = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Cancel(CancelMsg {
+                id: "secondswap".to_string(),
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // The guardian unpauses, trading resumes
+    let _res = app
+        .execute_contract(guardian, swap_inst.clone(), &ExecuteMsg::Unpause {}, &[])
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            arch_owner,
+            swap_inst,
+            &ExecuteMsg::Finish(finish_msg),
+            &payment,
+        )
+        .unwrap();
+}
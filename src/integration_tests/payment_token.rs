@@ -0,0 +1,216 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{create_cw20, create_cw721, create_swap, mock_app, query};
+use crate::msg::{
+    ExecuteMsg, PaymentTokenResponse, PaymentTokensResponse, QueryMsg, SwapMsg, UpdateMsg,
+};
+use crate::state::SwapType;
+use crate::ContractError;
+
+// Only allowlisted cw20s can be used for payment,
+// and swap prices must respect their minimum price
+#[test]
+fn test_payment_token_allowlist() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // cw20_owner owns the cw20
+    let cw20_owner = Addr::unchecked("cw20_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw20_owner creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(100000_u32),
+    );
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // Create a SwapMsg priced in the cw20
+    let swap_id: String = "firstswap".to_string();
+    let creation_msg = SwapMsg {
+        id: Some(swap_id.clone()),
+        payment_token: Some(cw20.clone()),
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(100_u32),
+        swap_type: SwapType::Sale,
+    };
+
+    // cw20 is not allowlisted
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PaymentTokenNotAllowed {
+            token: cw20.to_string(),
+        }
+    );
+
+    // Only the admin can allowlist a cw20
+    let add_msg = ExecuteMsg::AddPaymentToken {
+        address: cw20.to_string(),
+        min_price: Some(Uint128::from(1000_u32)),
+    };
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap_inst.clone(), &add_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // Non cw20 contracts cannot be allowlisted
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::AddPaymentToken {
+                address: swap_inst.to_string(),
+                min_price: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidPaymentToken {}
+    );
+
+    // swap_admin allowlists the cw20 with a minimum price
+    let _res = app
+        .execute_contract(swap_admin.clone(), swap_inst.clone(), &add_msg, &[])
+        .unwrap();
+    let tokens_query: PaymentTokensResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::PaymentTokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        tokens_query.tokens,
+        vec![PaymentTokenResponse {
+            address: cw20.clone(),
+            min_price: Some(Uint128::from(1000_u32)),
+        }]
+    );
+
+    // Swaps must respect the minimum price
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PriceTooLow {
+            price: Uint128::from(100_u32),
+            min_price: Uint128::from(1000_u32),
+        }
+    );
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                price: Uint128::from(1000_u32),
+                ..creation_msg
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // Updates must also respect the minimum price
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: swap_id,
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(999_u32),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::PriceTooLow {
+            price: Uint128::from(999_u32),
+            min_price: Uint128::from(1000_u32),
+        }
+    );
+
+    // swap_admin removes the cw20 from the allowlist
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::RemovePaymentToken {
+                address: cw20.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let tokens_query: PaymentTokensResponse = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::PaymentTokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(tokens_query.tokens.is_empty());
+}
@@ -0,0 +1,150 @@
+#![cfg(test)]
+use cosmwasm_std::Addr;
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{create_cw721, create_swap, mock_app, query};
+use crate::msg::{ExecuteMsg, HasRoleResponse, QueryMsg, RoleMembersResponse};
+use crate::state::{Config, Role};
+use crate::ContractError;
+
+// Roles are granted and revoked by the admin,
+// operators can change fees but not other config
+#[test]
+fn test_roles() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // operator can change fees
+    let operator = Addr::unchecked("operator");
+    // moderator cannot change fees
+    let moderator = Addr::unchecked("moderator");
+
+    // swap_admin creates the swap contract
+    let nft = create_cw721(&mut app, &swap_admin);
+    let swap_inst = create_swap(&mut app, &swap_admin, nft);
+
+    // Only the admin can grant roles
+    let grant_msg = ExecuteMsg::GrantRole {
+        role: Role::Operator,
+        address: operator.to_string(),
+    };
+    let err = app
+        .execute_contract(operator.clone(), swap_inst.clone(), &grant_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(swap_admin.clone(), swap_inst.clone(), &grant_msg, &[])
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::Moderator,
+                address: moderator.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let role_query: HasRoleResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::HasRole {
+            role: Role::Operator,
+            address: operator.to_string(),
+        },
+    )
+    .unwrap();
+    assert!(role_query.has_role);
+    let role_query: HasRoleResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::HasRole {
+            role: Role::Operator,
+            address: moderator.to_string(),
+        },
+    )
+    .unwrap();
+    assert!(!role_query.has_role);
+    let members_query: RoleMembersResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::RoleMembers {
+            role: Role::Moderator,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(members_query.members, vec![moderator.clone()]);
+
+    // Operators can change fees, other roles cannot
+    let err = app
+        .execute_contract(
+            moderator,
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 250 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let _res = app
+        .execute_contract(
+            operator.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetFees { fee_bps: 250 },
+            &[],
+        )
+        .unwrap();
+    let config_query: Config = query(&mut app, swap_inst.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config_query.fee_bps, 250);
+
+    // Operators cannot change other config
+    let err = app
+        .execute_contract(
+            operator.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::SetTreasury {
+                treasury: operator.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // swap_admin revokes the operator role
+    let _res = app
+        .execute_contract(
+            swap_admin,
+            swap_inst.clone(),
+            &ExecuteMsg::RevokeRole {
+                role: Role::Operator,
+                address: operator.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let err = app
+        .execute_contract(
+            operator,
+            swap_inst,
+            &ExecuteMsg::SetFees { fee_bps: 500 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+}
@@ -8,11 +8,11 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::contract::DENOM;
 use crate::integration_tests::util::{
-    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query,
+    add_payment_token, bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app,
+    query, DENOM,
 };
-use crate::msg::{ExecuteMsg, QueryMsg, SwapMsg};
+use crate::msg::{ExecuteMsg, FinishMsg, QueryMsg, SwapMsg};
 use crate::query::PageResult;
 use crate::state::SwapType;
 
@@ -57,14 +57,20 @@ fn test_buy_native() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
         swap_type: SwapType::Sale,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: DENOM.to_string(),
+        expected_revision: None,
+    };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
     let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
@@ -111,7 +117,7 @@ fn test_buy_native() {
     .unwrap();
 
     // cw721_owner has received the ARCH amount
-    let balance_query: Coin = bank_query(&mut app, &cw721_owner);
+    let balance_query: Coin = bank_query(&app, &cw721_owner);
 
     assert_eq!(owner_query.owner, arch_owner);
     assert_eq!(
@@ -124,7 +130,7 @@ fn test_buy_native() {
         &mut app,
         swap_inst,
         QueryMsg::ListingsOfToken {
-            token_id: token_id,
+            token_id,
             swap_type: Some(SwapType::Sale),
             page: Some(1_u32),
             limit: None,
@@ -161,6 +167,7 @@ fn test_buy_cw20() {
         "tscw".to_string(),
         Uint128::from(100000_u32),
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -178,14 +185,20 @@ fn test_buy_cw20() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         payment_token: Some(Addr::unchecked(cw20.clone())),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(100000_u32),
         swap_type: SwapType::Sale,
     };
-    let finish_msg = creation_msg.clone();
+    let finish_msg = FinishMsg {
+        id: creation_msg.id.clone().unwrap(),
+        expected_price: creation_msg.price,
+        expected_payment_token: creation_msg.payment_token.as_ref().unwrap().to_string(),
+        expected_revision: None,
+    };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
     let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
@@ -232,7 +245,7 @@ fn test_buy_cw20() {
         &mut app,
         nft.clone(),
         Cw721QueryMsg::OwnerOf {
-            token_id: token_id,
+            token_id,
             include_expired: None,
         },
     )
@@ -0,0 +1,490 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query, DENOM,
+};
+use crate::msg::{
+    CancelMsg, ExecuteMsg, QueryMsg, SearchCursor, SearchFilter, SearchResponse, SearchSort,
+    SwapMsg,
+};
+use crate::state::SwapType;
+
+fn search_ids(response: &SearchResponse) -> Vec<String> {
+    response
+        .swaps
+        .iter()
+        .map(|entry| entry.id.clone())
+        .collect()
+}
+
+// Search combines filters, sorts results and pages them with a cursor
+#[test]
+fn test_search() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns most of the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // other_seller owns one cw721
+    let other_seller = Addr::unchecked("other_seller");
+    // bidder makes an offer
+    let bidder = Addr::unchecked("bidder");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap_inst = create_swap(&mut app, &swap_admin, nft.clone());
+
+    // bidder creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(100000_u32),
+    );
+    add_payment_token(&mut app, &swap_admin, &swap_inst, &cw20);
+
+    // Names are listed by their owners, "abc.arch" expires at the next block
+    let height = app.block_info().height;
+    for (token_id, owner, price, expires) in [
+        (
+            "abc.arch",
+            &cw721_owner,
+            400_u32,
+            Expiration::AtHeight(height + 1),
+        ),
+        ("alice.arch", &cw721_owner, 300_u32, Expiration::Never {}),
+        ("alphabet.arch", &cw721_owner, 200_u32, Expiration::Never {}),
+        (
+            "bob.arch",
+            &cw721_owner,
+            100_u32,
+            Expiration::AtHeight(height + 100),
+        ),
+        ("zed.arch", &other_seller, 250_u32, Expiration::Never {}),
+    ] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+        let _res = app
+            .execute_contract(
+                owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(SwapMsg {
+                    id: Some(token_id.replace(".arch", "swap")),
+                    payment_token: None,
+                    denom: None,
+                    token_id: token_id.to_string(),
+                    expires,
+                    price: Uint128::from(price),
+                    swap_type: SwapType::Sale,
+                }),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // bidder offers 500 cw20 for "bob.arch"
+    let _res = app
+        .execute_contract(
+            bidder,
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("bidoffer".to_string()),
+                payment_token: Some(cw20.clone()),
+                denom: None,
+                token_id: "bob.arch".to_string(),
+                expires: Expiration::Never {},
+                price: Uint128::from(500_u32),
+                swap_type: SwapType::Offer,
+            }),
+            &[],
+        )
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    // Listings in the default denom priced 200 - 400, cheapest first;
+    // the expired listing is skipped unless requested
+    let price_filter = SearchFilter {
+        swap_type: Some(SwapType::Sale),
+        denom: Some(DENOM.to_string()),
+        min_price: Some(Uint128::from(200_u32)),
+        max_price: Some(Uint128::from(400_u32)),
+        ..SearchFilter::default()
+    };
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: price_filter.clone(),
+            sort: Some(SearchSort::PriceAscending),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["alphabetswap", "zedswap", "aliceswap"]
+    );
+    assert_eq!(search_query.next, None);
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                include_expired: Some(true),
+                ..price_filter.clone()
+            },
+            sort: Some(SearchSort::PriceAscending),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["alphabetswap", "zedswap", "aliceswap", "abcswap"]
+    );
+
+    // The same listings a page at a time
+    let mut ids: Vec<String> = vec![];
+    let mut start_after = None;
+    loop {
+        let search_query: SearchResponse = query(
+            &mut app,
+            swap_inst.clone(),
+            QueryMsg::Search {
+                filter: price_filter.clone(),
+                sort: Some(SearchSort::PriceAscending),
+                start_after,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+        ids.extend(search_ids(&search_query));
+        start_after = search_query.next;
+        if start_after.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, vec!["alphabetswap", "zedswap", "aliceswap"]);
+
+    // Offers in the cw20
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                swap_type: Some(SwapType::Offer),
+                denom: Some(cw20.to_string()),
+                ..SearchFilter::default()
+            },
+            sort: Some(SearchSort::PriceDescending),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(search_ids(&search_query), vec!["bidoffer"]);
+
+    // Names by cw721_owner starting with "al", at most 5 characters long
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                creator: Some(cw721_owner.to_string()),
+                name_prefix: Some("al".to_string()),
+                max_name_length: Some(5),
+                ..SearchFilter::default()
+            },
+            sort: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(search_ids(&search_query), vec!["aliceswap"]);
+
+    // Swaps of any type expiring by a given height
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                expires_before: Some(Expiration::AtHeight(height + 1000)),
+                ..SearchFilter::default()
+            },
+            sort: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(search_ids(&search_query), vec!["bobswap"]);
+
+    // Time bounds skip swaps expiring at a height, but not those that never expire
+    let time = app.block_info().time;
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                expires_after: Some(Expiration::AtTime(time)),
+                ..SearchFilter::default()
+            },
+            sort: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["aliceswap", "alphabetswap", "bidoffer", "zedswap"]
+    );
+
+    // Height and time bounds can't be combined
+    let err = query::<_, SearchResponse>(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                expires_after: Some(Expiration::AtHeight(height)),
+                expires_before: Some(Expiration::AtTime(time)),
+                ..SearchFilter::default()
+            },
+            sort: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("expires_after and expires_before must both be heights or both be times"));
+
+    // Pages of all active swaps, most expensive first
+    let mut ids: Vec<String> = vec![];
+    let mut start_after = None;
+    loop {
+        let search_query: SearchResponse = query(
+            &mut app,
+            swap_inst.clone(),
+            QueryMsg::Search {
+                filter: SearchFilter::default(),
+                sort: Some(SearchSort::PriceDescending),
+                start_after,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+        assert!(search_query.swaps.len() <= 2);
+        ids.extend(search_ids(&search_query));
+        start_after = search_query.next;
+        if start_after.is_none() {
+            break;
+        }
+    }
+    assert_eq!(
+        ids,
+        vec![
+            "bidoffer",
+            "aliceswap",
+            "zedswap",
+            "alphabetswap",
+            "bobswap"
+        ]
+    );
+}
+
+// Listings are found by name prefix, substring, TLD and length,
+// in name order and paged with a cursor
+#[test]
+fn test_search_names() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap_inst = create_swap(&mut app, &swap_admin, nft.clone());
+
+    // cw721_owner lists its names, "alice.arch" twice
+    for (id, token_id) in [
+        ("aliceswap", "alice.arch"),
+        ("aliceswap2", "alice.arch"),
+        ("aliciaswap", "alicia.arch"),
+        ("alswap", "al.xyz"),
+        ("bobswap", "bob.arch"),
+        ("maliceswap", "malice.arch"),
+    ] {
+        if id != "aliceswap2" {
+            let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+                token_id: token_id.to_string(),
+                owner: cw721_owner.to_string(),
+                token_uri: None,
+                extension: None,
+            });
+            let _res = app
+                .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+                .unwrap();
+        }
+        let _res = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(SwapMsg {
+                    id: Some(id.to_string()),
+                    payment_token: None,
+                    denom: None,
+                    token_id: token_id.to_string(),
+                    expires: Expiration::Never {},
+                    price: Uint128::from(100_u32),
+                    swap_type: SwapType::Sale,
+                }),
+                &[],
+            )
+            .unwrap();
+    }
+
+    let search_names = |app: &mut App,
+                        prefix: Option<&str>,
+                        contains: Option<&str>,
+                        tld: Option<&str>,
+                        start_after: Option<SearchCursor>,
+                        limit: Option<u32>|
+     -> SearchResponse {
+        query(
+            app,
+            swap_inst.clone(),
+            QueryMsg::SearchNames {
+                prefix: prefix.map(String::from),
+                contains: contains.map(String::from),
+                tld: tld.map(String::from),
+                min_length: None,
+                max_length: None,
+                start_after,
+                limit,
+            },
+        )
+        .unwrap()
+    };
+
+    // Names starting with "al"
+    let search_query = search_names(&mut app, Some("al"), None, None, None, None);
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["alswap", "aliceswap", "aliceswap2", "aliciaswap"]
+    );
+
+    // Names containing "lic" with the "arch" TLD
+    let search_query = search_names(&mut app, None, Some("lic"), Some(".arch"), None, None);
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["aliceswap", "aliceswap2", "aliciaswap", "maliceswap"]
+    );
+
+    // Names of 3 to 5 characters
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::SearchNames {
+            prefix: None,
+            contains: None,
+            tld: None,
+            min_length: Some(3),
+            max_length: Some(5),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["aliceswap", "aliceswap2", "bobswap"]
+    );
+
+    // Search uses the name index for prefixes
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                name_prefix: Some("ali".to_string()),
+                ..SearchFilter::default()
+            },
+            sort: Some(SearchSort::Name),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        search_ids(&search_query),
+        vec!["aliceswap", "aliceswap2", "aliciaswap"]
+    );
+
+    // ...and resumes from the cursor's name and swap id
+    let search_query: SearchResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Search {
+            filter: SearchFilter {
+                name_prefix: Some("ali".to_string()),
+                ..SearchFilter::default()
+            },
+            sort: Some(SearchSort::Name),
+            start_after: search_query.swaps.first().map(|entry| SearchCursor {
+                id: entry.id.clone(),
+                price: entry.swap.price,
+                token_id: entry.swap.token_id.clone(),
+            }),
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(search_ids(&search_query), vec!["aliceswap2"]);
+    assert!(search_query.next.is_some());
+
+    // Pages of names starting with "al", resuming between swaps of the same name
+    let first_page = search_names(&mut app, Some("al"), None, None, None, Some(2));
+    assert_eq!(search_ids(&first_page), vec!["alswap", "aliceswap"]);
+    let second_page = search_names(&mut app, Some("al"), None, None, first_page.next, Some(2));
+    assert_eq!(search_ids(&second_page), vec!["aliceswap2", "aliciaswap"]);
+    assert_eq!(second_page.next, None);
+
+    // Cancelled listings are no longer found
+    let _res = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Cancel(CancelMsg {
+                id: "bobswap".to_string(),
+            }),
+            &[],
+        )
+        .unwrap();
+    let search_query = search_names(&mut app, Some("b"), None, None, None, None);
+    assert!(search_query.swaps.is_empty());
+}
@@ -0,0 +1,237 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{
+    ExecuteMsg, FinishMsg, LastSaleResponse, QueryMsg, StatsResponse, SwapMsg, TokenAmount,
+    UpdateMsg,
+};
+use crate::state::{LastSale, SwapType};
+
+// Sales count, volume, last sale and floor price
+// are kept up to date as swaps change
+#[test]
+fn test_stats() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+    );
+
+    // cw721_owner mints and lists two cw721s, for 1 and 2 ARCH
+    for (token_id, price) in [
+        ("petrify", 1000000000000000000_u128),
+        ("ossify", 2000000000000000000_u128),
+    ] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: cw721_owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+        let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+            spender: swap.to_string(),
+            token_id: token_id.to_string(),
+            expires: None,
+        };
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &nft_approve_msg, &[])
+            .unwrap();
+        let creation_msg = SwapMsg {
+            id: Some(format!("{}swap", token_id)),
+            payment_token: None,
+            denom: None,
+            token_id: token_id.to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(price),
+            swap_type: SwapType::Sale,
+        };
+        let _res = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(creation_msg),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Floor is the cheapest listing, nothing has sold yet
+    let stats_query: StatsResponse =
+        query(&mut app, swap_inst.clone(), QueryMsg::Stats {}).unwrap();
+    assert_eq!(
+        stats_query,
+        StatsResponse {
+            sales: 0,
+            volume: vec![],
+            floor: vec![TokenAmount {
+                payment_token: DENOM.to_string(),
+                amount: Uint128::from(1000000000000000000_u128),
+            }],
+        }
+    );
+
+    // Buyer purchases the cheapest listing
+    let _res = app
+        .execute_contract(
+            arch_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(FinishMsg {
+                id: "petrifyswap".to_string(),
+                expected_price: Uint128::from(1000000000000000000_u128),
+                expected_payment_token: DENOM.to_string(),
+                expected_revision: None,
+            }),
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128),
+            }],
+        )
+        .unwrap();
+
+    // Seller raises the remaining listing to 3 ARCH
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: "ossifyswap".to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(3000000000000000000_u128),
+            }),
+            &[],
+        )
+        .unwrap();
+
+    let stats_query: StatsResponse =
+        query(&mut app, swap_inst.clone(), QueryMsg::Stats {}).unwrap();
+    assert_eq!(
+        stats_query,
+        StatsResponse {
+            sales: 1,
+            volume: vec![TokenAmount {
+                payment_token: DENOM.to_string(),
+                amount: Uint128::from(1000000000000000000_u128),
+            }],
+            floor: vec![TokenAmount {
+                payment_token: DENOM.to_string(),
+                amount: Uint128::from(3000000000000000000_u128),
+            }],
+        }
+    );
+
+    let last_sale_query: LastSaleResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::LastSale {
+            token_id: "petrify".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        last_sale_query.last_sale,
+        Some(LastSale {
+            swap_id: "petrifyswap".to_string(),
+            price: Uint128::from(1000000000000000000_u128),
+            payment_token: DENOM.to_string(),
+            height: app.block_info().height,
+        })
+    );
+    let last_sale_query: LastSaleResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::LastSale {
+            token_id: "ossify".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(last_sale_query.last_sale, None);
+
+    // cw721_owner lists a third cw721 for 1 ARCH, then transfers it away
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: "calcify".to_string(),
+        owner: cw721_owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: "calcify".to_string(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+    let creation_msg = SwapMsg {
+        id: Some("calcifyswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id: "calcify".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128),
+        swap_type: SwapType::Sale,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+    let stats_query: StatsResponse =
+        query(&mut app, swap_inst.clone(), QueryMsg::Stats {}).unwrap();
+    assert_eq!(
+        stats_query.floor,
+        vec![TokenAmount {
+            payment_token: DENOM.to_string(),
+            amount: Uint128::from(1000000000000000000_u128),
+        }]
+    );
+
+    // The stale listing no longer counts towards the floor
+    let transfer_msg = Cw721ExecuteMsg::<Extension>::TransferNft {
+        recipient: "someone_else".to_string(),
+        token_id: "calcify".to_string(),
+    };
+    let _res = app
+        .execute_contract(cw721_owner, nft, &transfer_msg, &[])
+        .unwrap();
+    let stats_query: StatsResponse = query(&mut app, swap_inst, QueryMsg::Stats {}).unwrap();
+    assert_eq!(
+        stats_query.floor,
+        vec![TokenAmount {
+            payment_token: DENOM.to_string(),
+            amount: Uint128::from(3000000000000000000_u128),
+        }]
+    );
+}
@@ -0,0 +1,127 @@
+#![cfg(test)]
+use cosmwasm_std::{from_binary, Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{create_cw721, create_swap, mock_app, query};
+use crate::msg::{CreateResponse, ExecuteMsg, ListResponse, QueryMsg, SwapMsg};
+use crate::state::SwapType;
+use crate::ContractError;
+
+// Swaps created without an id are assigned one from a counter,
+// and the id is returned in the response data
+#[test]
+fn test_swap_ids() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft, &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates two swaps without an id
+    let creation_msg = SwapMsg {
+        id: None,
+        payment_token: None,
+        denom: None,
+        token_id,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    for expected_id in ["1", "2"] {
+        let res = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(creation_msg.clone()),
+                &[],
+            )
+            .unwrap();
+        let data: CreateResponse = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(data.id, expected_id);
+        let wasm_event = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+        assert!(wasm_event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "swap_id" && attr.value == expected_id));
+    }
+
+    // Numeric custom ids are reserved
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("3".to_string()),
+                ..creation_msg.clone()
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidId {}
+    );
+
+    // Other custom ids are accepted
+    let res = app
+        .execute_contract(
+            cw721_owner,
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("firstswap".to_string()),
+                ..creation_msg
+            }),
+            &[],
+        )
+        .unwrap();
+    let data: CreateResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(data.id, "firstswap");
+
+    let list_query: ListResponse = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        list_query.swaps,
+        vec!["1".to_string(), "2".to_string(), "firstswap".to_string()]
+    );
+}
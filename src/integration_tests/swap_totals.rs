@@ -0,0 +1,202 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query, DENOM,
+};
+use crate::msg::{CancelMsg, ExecuteMsg, QueryMsg, SwapMsg};
+use crate::state::SwapType;
+
+// Swap totals are maintained on create and cancel,
+// and can be filtered and combined
+#[test]
+fn test_swap_totals() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // cw20_owner owns the cw20
+    let cw20_owner = Addr::unchecked("cw20_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw20_owner creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(100000_u32),
+    );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
+
+    // cw721_owner mints two cw721s
+    for token_id in ["petrify", "ossify"] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: cw721_owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+    }
+
+    // cw721_owner lists both tokens for ARCH, and ossify again for cw20
+    // (petrify's listing expires at the next block);
+    // cw20_owner makes two offers on petrify
+    let height = app.block_info().height;
+    let swaps = [
+        (
+            "petrifysale",
+            &cw721_owner,
+            "petrify",
+            None,
+            SwapType::Sale,
+            Expiration::AtHeight(height + 1),
+        ),
+        (
+            "ossifysale",
+            &cw721_owner,
+            "ossify",
+            None,
+            SwapType::Sale,
+            Expiration::Never {},
+        ),
+        (
+            "ossifycw20sale",
+            &cw721_owner,
+            "ossify",
+            Some(cw20.clone()),
+            SwapType::Sale,
+            Expiration::Never {},
+        ),
+        (
+            "petrifyoffer",
+            &cw20_owner,
+            "petrify",
+            Some(cw20.clone()),
+            SwapType::Offer,
+            Expiration::Never {},
+        ),
+        (
+            "petrifyoffer2",
+            &cw20_owner,
+            "petrify",
+            Some(cw20.clone()),
+            SwapType::Offer,
+            Expiration::Never {},
+        ),
+    ];
+    for (id, creator, token_id, payment_token, swap_type, expires) in swaps {
+        let creation_msg = SwapMsg {
+            id: Some(id.to_string()),
+            payment_token,
+            denom: None,
+            token_id: token_id.to_string(),
+            expires,
+            price: Uint128::from(1000_u32),
+            swap_type,
+        };
+        let _res = app
+            .execute_contract(
+                creator.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(creation_msg),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // cw20_owner cancels one offer
+    let _res = app
+        .execute_contract(
+            cw20_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Cancel(CancelMsg {
+                id: "petrifyoffer2".to_string(),
+            }),
+            &[],
+        )
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    let mut total = |swap_type: SwapType,
+                     denom: Option<String>,
+                     creator: Option<&Addr>,
+                     token_id: Option<&str>,
+                     include_expired: Option<bool>|
+     -> u128 {
+        query(
+            &mut app,
+            swap_inst.clone(),
+            QueryMsg::GetTotal {
+                swap_type,
+                denom,
+                creator: creator.map(|creator| creator.to_string()),
+                collection: None,
+                token_id: token_id.map(|token_id| token_id.to_string()),
+                include_expired,
+            },
+        )
+        .unwrap()
+    };
+
+    // Maintained counts
+    assert_eq!(total(SwapType::Sale, None, None, None, None), 3);
+    assert_eq!(total(SwapType::Offer, None, None, None, None), 1);
+    assert_eq!(
+        total(SwapType::Sale, Some(DENOM.to_string()), None, None, None),
+        2
+    );
+    assert_eq!(
+        total(SwapType::Offer, None, Some(&cw20_owner), None, None),
+        1
+    );
+    assert_eq!(total(SwapType::Sale, None, None, Some("ossify"), None), 2);
+
+    // Combined filters and expiry
+    assert_eq!(
+        total(
+            SwapType::Sale,
+            Some(cw20.to_string()),
+            Some(&cw721_owner),
+            None,
+            None
+        ),
+        1
+    );
+    assert_eq!(total(SwapType::Sale, None, None, None, Some(false)), 2);
+    assert_eq!(
+        total(SwapType::Sale, None, None, Some("petrify"), Some(false)),
+        0
+    );
+
+    // Other collections have no swaps
+    let total: u128 = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::GetTotal {
+            swap_type: SwapType::Sale,
+            denom: None,
+            creator: None,
+            collection: Some(swap_inst.to_string()),
+            token_id: None,
+            include_expired: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(total, 0);
+}
@@ -0,0 +1,188 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{ExecuteMsg, FinishMsg, QueryMsg, SwapMsg, TradesResponse};
+use crate::state::SwapType;
+
+// Finished swaps leave a trade record, queryable per token
+// and across all tokens
+#[test]
+fn test_trade_history() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // Mint native to both traders
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(3000000000000000000_u128), // 3 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        cw721_owner.to_string(),
+        Uint128::from(2000000000000000000_u128), // 2 ARCH as aarch
+    );
+
+    // cw721_owner mints two cw721s
+    for token_id in ["petrify", "ossify"] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: cw721_owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+    }
+
+    // `seller` lists `token_id` for `price`, and `buyer` buys it
+    let mut trade = |seller: &Addr, buyer: &Addr, id: &str, token_id: &str, price: u128| {
+        let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+            spender: swap.to_string(),
+            token_id: token_id.to_string(),
+            expires: None,
+        };
+        let _res = app
+            .execute_contract(seller.clone(), nft.clone(), &nft_approve_msg, &[])
+            .unwrap();
+        let creation_msg = SwapMsg {
+            id: Some(id.to_string()),
+            payment_token: None,
+            denom: None,
+            token_id: token_id.to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(price),
+            swap_type: SwapType::Sale,
+        };
+        let _res = app
+            .execute_contract(
+                seller.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Create(creation_msg),
+                &[],
+            )
+            .unwrap();
+        let finish_msg = FinishMsg {
+            id: id.to_string(),
+            expected_price: Uint128::from(price),
+            expected_payment_token: DENOM.to_string(),
+            expected_revision: None,
+        };
+        let _res = app
+            .execute_contract(
+                buyer.clone(),
+                swap_inst.clone(),
+                &ExecuteMsg::Finish(finish_msg),
+                &[Coin {
+                    denom: String::from(DENOM),
+                    amount: Uint128::from(price),
+                }],
+            )
+            .unwrap();
+        app.update_block(|block| block.height += 1);
+    };
+
+    // petrify is sold, then bought back; ossify is sold once
+    trade(
+        &cw721_owner,
+        &arch_owner,
+        "firstswap",
+        "petrify",
+        1000000000000000000,
+    );
+    trade(
+        &arch_owner,
+        &cw721_owner,
+        "secondswap",
+        "petrify",
+        2000000000000000000,
+    );
+    trade(
+        &cw721_owner,
+        &arch_owner,
+        "thirdswap",
+        "ossify",
+        2000000000000000000,
+    );
+
+    // Token history is newest first
+    let history_query: TradesResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::TradeHistory {
+            token_id: "petrify".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(history_query.trades.len(), 2);
+    let latest = &history_query.trades[0];
+    assert_eq!(latest.id, 2);
+    assert_eq!(latest.swap_id, "secondswap");
+    assert_eq!(latest.seller, arch_owner);
+    assert_eq!(latest.buyer, cw721_owner);
+    assert_eq!(latest.price, Uint128::from(2000000000000000000_u128));
+    assert_eq!(latest.payment_token, DENOM);
+    assert_eq!(latest.swap_type, SwapType::Sale);
+    let earliest = &history_query.trades[1];
+    assert_eq!(earliest.id, 1);
+    assert_eq!(earliest.seller, cw721_owner);
+    assert_eq!(earliest.buyer, arch_owner);
+    assert!(earliest.height < latest.height);
+
+    // Paginate past the latest trade
+    let history_query: TradesResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::TradeHistory {
+            token_id: "petrify".to_string(),
+            start_after: Some(2),
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(history_query.trades.len(), 1);
+    assert_eq!(history_query.trades[0].id, 1);
+
+    // Recent trades span all tokens
+    let recent_query: TradesResponse = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::RecentTrades {
+            start_after: None,
+            limit: Some(2),
+        },
+    )
+    .unwrap();
+    let recent: Vec<(u64, String)> = recent_query
+        .trades
+        .into_iter()
+        .map(|trade| (trade.id, trade.token_id))
+        .collect();
+    assert_eq!(
+        recent,
+        vec![(3, "ossify".to_string()), (2, "petrify".to_string())]
+    );
+}
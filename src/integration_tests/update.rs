@@ -5,7 +5,9 @@ use cw_multi_test::Executor;
 use cw20::{Cw20ExecuteMsg, Expiration};
 use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
 
-use crate::integration_tests::util::{create_cw20, create_cw721, create_swap, mock_app, query};
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mock_app, query,
+};
 use crate::msg::{DetailsResponse, ExecuteMsg, QueryMsg, SwapMsg, UpdateMsg};
 use crate::state::SwapType;
 
@@ -42,10 +44,11 @@ fn test_updating_sales() {
     // Create a SwapMsg for creating / finishing a swap
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         payment_token: None,
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
         swap_type: SwapType::Sale,
     };
@@ -80,14 +83,14 @@ fn test_updating_sales() {
     .unwrap();
     assert_eq!(
         swap_details.expires,
-        Expiration::from(cw20::Expiration::AtHeight(384798573487439743))
+        Expiration::AtHeight(384798573487439743)
     );
     assert_eq!(swap_details.price, Uint128::from(1000000000000000000_u128));
 
     // cw721 seller (cw721_owner) updates the swap
     let update_msg = UpdateMsg {
         id: swap_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(400000000000000000)),
+        expires: Expiration::AtHeight(400000000000000000),
         price: Uint128::from(2000000000000000000_u128),
     };
     let _res = app
@@ -110,7 +113,7 @@ fn test_updating_sales() {
     .unwrap();
     assert_eq!(
         swap_details.expires,
-        Expiration::from(cw20::Expiration::AtHeight(400000000000000000))
+        Expiration::AtHeight(400000000000000000)
     );
     assert_eq!(swap_details.price, Uint128::from(2000000000000000000_u128));
 }
@@ -142,6 +145,7 @@ fn test_updating_offers() {
         "wARCH".to_string(),
         Uint128::from(9000000000000000000_u128), // 9 wARCH
     );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
     let cw20_inst = cw20.clone();
 
     // cw721_owner mints a cw721
@@ -170,10 +174,11 @@ fn test_updating_offers() {
     // Bidding buyer creates an offer
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         payment_token: Some(Addr::unchecked(cw20)),
+        denom: None,
         token_id: token_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        expires: Expiration::AtHeight(384798573487439743),
         price: Uint128::from(9000000000000000000_u128), // 9 wARCH
         swap_type: SwapType::Offer,
     };
@@ -199,14 +204,14 @@ fn test_updating_offers() {
 
     assert_eq!(
         swap_details.expires,
-        Expiration::from(cw20::Expiration::AtHeight(384798573487439743))
+        Expiration::AtHeight(384798573487439743)
     );
     assert_eq!(swap_details.price, Uint128::from(9000000000000000000_u128));
 
     // Bidder (cw20_owner) updates the swap
     let update_msg = UpdateMsg {
         id: swap_id.clone(),
-        expires: Expiration::from(cw20::Expiration::AtHeight(400000000000000000)),
+        expires: Expiration::AtHeight(400000000000000000),
         price: Uint128::from(1000000000000000000_u128),
     };
     let _res = app
@@ -230,7 +235,7 @@ fn test_updating_offers() {
 
     assert_eq!(
         swap_details.expires,
-        Expiration::from(cw20::Expiration::AtHeight(400000000000000000))
+        Expiration::AtHeight(400000000000000000)
     );
     assert_eq!(swap_details.price, Uint128::from(1000000000000000000_u128));
 }
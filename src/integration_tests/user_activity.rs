@@ -0,0 +1,193 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::integration_tests::util::{
+    add_payment_token, create_cw20, create_cw721, create_swap, mint_native, mock_app, query, DENOM,
+};
+use crate::msg::{ExecuteMsg, FinishMsg, QueryMsg, SwapEntry, SwapMsg, UserActivityResponse};
+use crate::state::SwapType;
+
+// User activity gathers active swaps created by an address,
+// offers on tokens it owns, and its completed trades
+#[test]
+fn test_user_activity() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721s
+    let cw721_owner = Addr::unchecked("original_owner");
+    // arch_owner owns ARCH
+    let arch_owner = Addr::unchecked("arch_owner");
+    // cw20_owner owns the cw20
+    let cw20_owner = Addr::unchecked("cw20_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw20_owner creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(100000_u32),
+    );
+    add_payment_token(&mut app, &swap_admin, &swap, &cw20);
+
+    // Mint native to `arch_owner`
+    mint_native(
+        &mut app,
+        arch_owner.to_string(),
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+    );
+
+    // cw721_owner mints three cw721s, approving the swap contract to spend them
+    for token_id in ["petrify", "ossify", "calcify"] {
+        let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: cw721_owner.to_string(),
+            token_uri: None,
+            extension: None,
+        });
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+            .unwrap();
+        let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+            spender: swap.to_string(),
+            token_id: token_id.to_string(),
+            expires: None,
+        };
+        let _res = app
+            .execute_contract(cw721_owner.clone(), nft.clone(), &nft_approve_msg, &[])
+            .unwrap();
+    }
+
+    // cw721_owner lists ossify and calcify for 1 ARCH
+    let creation_msg = SwapMsg {
+        id: Some("ossifyswap".to_string()),
+        payment_token: None,
+        denom: None,
+        token_id: "ossify".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg.clone()),
+            &[],
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some("calcifyswap".to_string()),
+                token_id: "calcify".to_string(),
+                ..creation_msg
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // cw20_owner makes an offer on petrify
+    let offer_msg = SwapMsg {
+        id: Some("petrifyoffer".to_string()),
+        payment_token: Some(cw20.clone()),
+        denom: None,
+        token_id: "petrify".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(10000_u32),
+        swap_type: SwapType::Offer,
+    };
+    let _res = app
+        .execute_contract(
+            cw20_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(offer_msg),
+            &[],
+        )
+        .unwrap();
+
+    // arch_owner buys ossify
+    let _res = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Finish(FinishMsg {
+                id: "ossifyswap".to_string(),
+                expected_price: Uint128::from(1000000000000000000_u128),
+                expected_payment_token: DENOM.to_string(),
+                expected_revision: None,
+            }),
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128),
+            }],
+        )
+        .unwrap();
+
+    // Seller has an active listing, an offer received and a sale
+    let activity_query: UserActivityResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::UserActivity {
+            address: cw721_owner.to_string(),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let ids = |entries: &[SwapEntry]| -> Vec<String> {
+        entries.iter().map(|entry| entry.id.clone()).collect()
+    };
+    assert_eq!(ids(&activity_query.listings), vec!["calcifyswap"]);
+    assert!(activity_query.offers_made.is_empty());
+    assert_eq!(ids(&activity_query.offers_received), vec!["petrifyoffer"]);
+    assert!(activity_query.purchases.is_empty());
+    assert_eq!(activity_query.sales.len(), 1);
+    assert_eq!(activity_query.sales[0].swap_id, "ossifyswap");
+    assert_eq!(activity_query.sales[0].buyer, arch_owner);
+
+    // Bidder has an offer made
+    let activity_query: UserActivityResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::UserActivity {
+            address: cw20_owner.to_string(),
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(activity_query.listings.is_empty());
+    assert_eq!(ids(&activity_query.offers_made), vec!["petrifyoffer"]);
+    assert!(activity_query.offers_received.is_empty());
+
+    // Buyer has a purchase
+    let activity_query: UserActivityResponse = query(
+        &mut app,
+        swap_inst,
+        QueryMsg::UserActivity {
+            address: arch_owner.to_string(),
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(activity_query.listings.is_empty());
+    assert!(activity_query.offers_received.is_empty());
+    assert!(activity_query.sales.is_empty());
+    assert_eq!(activity_query.purchases.len(), 1);
+    assert_eq!(activity_query.purchases[0].token_id, "ossify");
+    assert_eq!(activity_query.purchases[0].seller, cw721_owner);
+}
@@ -12,8 +12,10 @@ use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use cw20::Cw20Coin;
 use cw721_base::msg::InstantiateMsg as Cw721InstantiateMsg;
 
-use crate::contract::DENOM;
-use crate::msg::InstantiateMsg;
+use crate::msg::{ExecuteMsg, InstantiateMsg};
+
+// Native denom the swap contract is instantiated with
+pub static DENOM: &str = "aarch";
 
 pub fn mock_app() -> App {
     App::default()
@@ -50,12 +52,13 @@ pub fn create_swap(router: &mut App, owner: &Addr, cw721: Addr) -> Addr {
     let swap_id = router.store_code(contract_swap721());
     let msg = InstantiateMsg {
         admin: owner.clone(),
-        cw721: cw721,
+        cw721,
+        denom: DENOM.to_string(),
     };
-    let swap_addr = router
+
+    router
         .instantiate_contract(swap_id, owner.clone(), &msg, &[], "swap721", None)
-        .unwrap();
-    swap_addr
+        .unwrap()
 }
 
 pub fn create_cw721(router: &mut App, minter: &Addr) -> Addr {
@@ -65,19 +68,23 @@ pub fn create_cw721(router: &mut App, minter: &Addr) -> Addr {
         symbol: "TSNFT".to_string(),
         minter: String::from(minter),
     };
-    let contract = router
+
+    router
         .instantiate_contract(cw721_id, minter.clone(), &msg, &[], "swap721", None)
-        .unwrap();
-    contract
+        .unwrap()
 }
 
 pub fn mint_native(app: &mut App, beneficiary: String, amount: Uint128) {
+    mint_denom(app, beneficiary, DENOM, amount);
+}
+
+pub fn mint_denom(app: &mut App, beneficiary: String, denom: &str, amount: Uint128) {
     app.sudo(cw_multi_test::SudoMsg::Bank(
         cw_multi_test::BankSudo::Mint {
             to_address: beneficiary,
             amount: vec![Coin {
-                denom: DENOM.to_string(),
-                amount: amount,
+                denom: denom.to_string(),
+                amount,
             }],
         },
     ))
@@ -94,8 +101,8 @@ pub fn create_cw20(
     // set up cw20 contract with some tokens
     let cw20_id = router.store_code(contract_cw20());
     let msg = cw20_base::msg::InstantiateMsg {
-        name: name,
-        symbol: symbol,
+        name,
+        symbol,
         decimals: 2,
         initial_balances: vec![Cw20Coin {
             address: owner.to_string(),
@@ -104,10 +111,21 @@ pub fn create_cw20(
         mint: None,
         marketing: None,
     };
-    let addr = router
+
+    router
         .instantiate_contract(cw20_id, owner.clone(), &msg, &[], "CASH", None)
+        .unwrap()
+}
+
+// Allowlist a cw20 as a payment token of the swap contract
+pub fn add_payment_token(router: &mut App, admin: &Addr, swap: &Addr, cw20: &Addr) {
+    let msg = ExecuteMsg::AddPaymentToken {
+        address: cw20.to_string(),
+        min_price: None,
+    };
+    router
+        .execute_contract(admin.clone(), swap.clone(), &msg, &[])
         .unwrap();
-    addr
 }
 
 pub fn query<M, T>(router: &mut App, target_contract: Addr, msg: M) -> Result<T, StdError>
@@ -122,13 +140,17 @@ where
 }
 
 pub fn bank_query(app: &App, address: &Addr) -> Coin {
+    bank_query_denom(app, address, DENOM)
+}
+
+pub fn bank_query_denom(app: &App, address: &Addr, denom: &str) -> Coin {
     let req: QueryRequest<BankQuery> = QueryRequest::Bank(BankQuery::Balance {
         address: address.to_string(),
-        denom: DENOM.to_string(),
+        denom: denom.to_string(),
     });
     let res = app.raw_query(&to_binary(&req).unwrap()).unwrap().unwrap();
     let balance: BalanceResponseBank = from_binary(&res).unwrap();
-    return balance.amount;
+    balance.amount
 }
 
 pub fn has_unique_elements<T>(iter: T) -> bool
@@ -1,5 +1,6 @@
 pub mod contract;
 mod error;
+pub mod events;
 pub mod execute;
 mod integration_tests;
 pub mod msg;
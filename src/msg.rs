@@ -1,4 +1,4 @@
-use crate::state::{Config, SwapType};
+use crate::state::{CW721Swap, LastSale, Role, SwapType, Trade};
 use cosmwasm_std::{Addr, Uint128};
 use cw20::Expiration;
 use schemars::JsonSchema;
@@ -8,16 +8,110 @@ use serde::{Deserialize, Serialize};
 pub struct InstantiateMsg {
     pub admin: Addr,
     pub cw721: Addr,
+    pub denom: String, // Default native denom (e.g. `aarch` on mainnet, `aconst` on testnet)
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Create(SwapMsg),
-    Finish(SwapMsg),
+    Finish(FinishMsg),
     Cancel(CancelMsg),
     Update(UpdateMsg),
-    UpdateConfig { config: Config },
+    /// Admin only, set the cw721 collection traded in the marketplace
+    SetCollection {
+        cw721: String,
+    },
+    /// Admin or operator only, set the fee retained from each sale (in basis points, at most 1000)
+    SetFees {
+        fee_bps: u64,
+    },
+    /// Admin only, set the recipient of marketplace fees
+    SetTreasury {
+        treasury: String,
+    },
+    /// Admin only, set the default native denom
+    SetDenom {
+        denom: String,
+    },
+    /// Admin only, set the number of blocks after an update before a swap can be finished
+    /// (at most `MAX_UPDATE_DELAY`)
+    SetUpdateDelay {
+        blocks: u64,
+    },
+    /// Admin only, accept a native denom (e.g. an IBC denom) for payment
+    AddNativeDenom {
+        denom: String,
+    },
+    /// Admin only, stop accepting a native denom for new swaps
+    RemoveNativeDenom {
+        denom: String,
+    },
+    /// Admin only, accept a cw20 for payment with an optional minimum swap price
+    AddPaymentToken {
+        address: String,
+        min_price: Option<Uint128>,
+    },
+    /// Admin only, stop accepting a cw20 for new swaps
+    RemovePaymentToken {
+        address: String,
+    },
+    /// Admin only, propose a new admin (who must accept with `AcceptAdmin`)
+    ProposeAdmin {
+        address: String,
+    },
+    /// Pending admin only, accept the admin role
+    AcceptAdmin {},
+    /// Admin only, irreversibly give up the admin role and revoke all roles
+    /// (fails while paused)
+    RenounceAdmin {},
+    /// Admin only, grant a role to an address
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    /// Admin only, revoke a role from an address
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    /// Admin or moderator only, force-remove a swap
+    Delist {
+        id: String,
+        reason: String,
+    },
+    /// Admin or moderator only, block a token id from being listed
+    BlockName {
+        token_id: String,
+    },
+    /// Admin or moderator only, allow a blocked token id to be listed again
+    UnblockName {
+        token_id: String,
+    },
+    /// Admin or moderator only, block token ids containing `pattern` (case insensitive)
+    BlockPattern {
+        pattern: String,
+    },
+    /// Admin or moderator only, remove a blocked pattern
+    UnblockPattern {
+        pattern: String,
+    },
+    /// Admin only, block an address from creating or taking part in swaps
+    BlockAddress {
+        address: String,
+    },
+    /// Admin only, unblock an address
+    UnblockAddress {
+        address: String,
+    },
+    /// Remove swaps created by blocked addresses (swaps by other creators are skipped)
+    PruneBlocked {
+        ids: Vec<String>,
+    },
+    /// Admin, pauser or guardian only, halt `Create`, `Finish` and `Update`
+    Pause {},
+    /// Admin or guardian only, resume trading
+    Unpause {},
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -32,26 +126,48 @@ pub struct UpdateMsg {
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct SwapMsg {
-    pub id: String,
-    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` create swap for `aarch`
+    pub id: Option<String>, // Optional custom id (not all digits); if `None` an id is assigned on `Create`
+    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` create swap for a native denom
+    pub denom: Option<String>, // Optional native denom; if `None` (and no `payment_token`) create swap for the default native denom
     pub token_id: String,
     pub expires: Expiration,
     pub price: Uint128,
     pub swap_type: SwapType,
 }
 
+// Swaps settle from their stored terms; the expectations protect
+// the caller against terms changing before the `Finish` lands
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FinishMsg {
+    pub id: String,
+    pub expected_price: Uint128, // Max price paid for sales, min price received for offers
+    pub expected_payment_token: String, // cw20 address or native denom
+    pub expected_revision: Option<u64>, // Optional swap revision (see `DetailsResponse`)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    /// Get the marketplace config (admin, collection, denom and fees)
+    /// Return type: Config
+    Config {},
     /// Get all swaps (enumerable)
     /// Return type: ListResponse
     List {
         start_after: Option<String>,
         limit: Option<u32>,
     },
-    // Count total `SwapType::Offer` or `SwapType::Sale`
+    // Count total `SwapType::Offer` or `SwapType::Sale`,
+    // optionally filtered by payment token (cw20 address or native denom),
+    // creator, collection (defaults to the marketplace collection) and token id;
+    // expired swaps are counted unless `include_expired` is `Some(false)`
     GetTotal {
         swap_type: SwapType,
+        denom: Option<String>,
+        creator: Option<String>,
+        collection: Option<String>,
+        token_id: Option<String>,
+        include_expired: Option<bool>,
     },
     /// Get all swaps of type `SwapType::Offer`
     GetOffers {
@@ -87,15 +203,16 @@ pub enum QueryMsg {
         page: Option<u32>,
         limit: Option<u32>,
     },
-    /// Show all swaps of a given denom (contract address)
-    /// Defaults to ARCH if no contract is sent
+    /// Show all swaps of a given denom (contract address or native denom)
+    /// Defaults to the configured default denom if no contract or native denom is sent
     SwapsByDenom {
         payment_token: Option<Addr>,
+        denom: Option<String>,
         swap_type: Option<SwapType>,
         page: Option<u32>,
         limit: Option<u32>,
     },
-    /// Show all cw20 swaps, or all ARCH swaps
+    /// Show all cw20 swaps, or all native denom swaps
     SwapsByPaymentType {
         cw20: bool,
         swap_type: Option<SwapType>,
@@ -105,14 +222,166 @@ pub enum QueryMsg {
 
     /// Returns the details of the named swap, error if not created.
     /// Return type: DetailsResponse.
-    Details {
-        id: String,
+    Details { id: String },
+    /// Returns the details of many swaps, `None` for ids that do not exist
+    /// (at most 100 ids)
+    /// Return type: DetailsManyResponse
+    DetailsMany { ids: Vec<String> },
+    /// List native denoms accepted for payment, besides the default native denom
+    /// Return type: NativeDenomsResponse
+    NativeDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List cw20 tokens accepted for payment
+    /// Return type: PaymentTokensResponse
+    PaymentTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Admin proposed by the current admin, if any
+    /// Return type: PendingAdminResponse
+    PendingAdmin {},
+    /// Whether trading is paused
+    /// Return type: PauseStatusResponse
+    PauseStatus {},
+    /// List token ids blocked from listing
+    /// Return type: BlockedNamesResponse
+    BlockedNames {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List patterns blocking token ids from listing
+    /// Return type: BlockedPatternsResponse
+    BlockedPatterns {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List addresses blocked from creating or taking part in swaps
+    /// Return type: BlockedAddressesResponse
+    BlockedAddresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Whether an address has been granted a role
+    /// Return type: HasRoleResponse
+    HasRole { role: Role, address: String },
+    /// List addresses granted a role
+    /// Return type: RoleMembersResponse
+    RoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Marketplace aggregates: sales count, volume and floor price per payment token
+    /// Return type: StatsResponse
+    Stats {},
+    /// Most recent sale of a token id, if any
+    /// Return type: LastSaleResponse
+    LastSale { token_id: String },
+    /// Trades of a token id, newest first
+    /// Return type: TradesResponse
+    TradeHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Trades of all tokens, newest first
+    /// Return type: TradesResponse
+    RecentTrades {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Active listings, offers made and received, and completed trades of an address
+    /// (up to `limit` of each, trades newest first)
+    /// Return type: UserActivityResponse
+    UserActivity { address: String, limit: Option<u32> },
+    /// Active offers on tokens held by `owner`, optionally in a single payment token
+    /// (cw20 address or native denom); grouped by payment token, highest price first
+    /// Return type: OffersReceivedResponse
+    OffersReceived {
+        owner: String,
+        payment_token: Option<String>,
+        page: Option<u32>,
+        limit: Option<u32>,
     },
+    /// Lowest valid sale and highest valid offer of a token, per payment token
+    /// Return type: BestPricesResponse
+    BestPrices { token_id: String },
+    /// Search swaps matching all of the given filters, ordered by `sort`
+    /// (defaults to swap id); pass the `next` cursor of a page as `start_after`
+    /// to fetch the following page
+    /// Return type: SearchResponse
+    Search {
+        filter: SearchFilter,
+        sort: Option<SearchSort>,
+        start_after: Option<SearchCursor>,
+        limit: Option<u32>,
+    },
+    /// Find active listings by name: names starting with `prefix`, containing
+    /// `contains`, ending in the TLD `tld` (e.g. `arch`) and with a length
+    /// (excluding the TLD) within `min_length` - `max_length`, ordered by name;
+    /// pass the `next` cursor of a page as `start_after` to fetch the following page
+    /// Return type: SearchResponse
+    SearchNames {
+        prefix: Option<String>,
+        contains: Option<String>,
+        tld: Option<String>,
+        min_length: Option<u32>,
+        max_length: Option<u32>,
+        start_after: Option<SearchCursor>,
+        limit: Option<u32>,
+    },
+}
+
+// Filters of a `Search` query, unset filters match every swap
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct SearchFilter {
+    pub swap_type: Option<SwapType>,
+    pub collection: Option<String>, // Defaults to the marketplace collection
+    pub creator: Option<String>,
+    pub denom: Option<String>, // Payment token (cw20 address or native denom)
+    pub min_price: Option<Uint128>,
+    pub max_price: Option<Uint128>,
+    // Expiry bounds only match swaps expiring in the same kind (height or time)
+    pub expires_after: Option<Expiration>, // Swaps expiring at or after
+    pub expires_before: Option<Expiration>, // Swaps expiring at or before
+    pub include_expired: Option<bool>,     // Expired swaps are skipped by default
+    pub min_name_length: Option<u32>,      // Name length excludes the TLD
+    pub max_name_length: Option<u32>,
+    pub name_prefix: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    Id,
+    PriceAscending,
+    PriceDescending,
+    Name,
+}
+
+// Position of the last swap of a `Search` page
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SearchCursor {
+    pub id: String,
+    pub price: Uint128,
+    pub token_id: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // Default native denom, required when migrating
+    // from a version without a configurable denom
+    pub denom: Option<String>,
+}
+
+// Data of the `Create` response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateResponse {
+    pub id: String,
+}
 
 // List swaps
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -120,14 +389,163 @@ pub struct ListResponse {
     pub swaps: Vec<String>,
 }
 
+// List native denoms accepted for payment
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NativeDenomsResponse {
+    pub denoms: Vec<String>,
+}
+
+// List cw20 tokens accepted for payment
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentTokensResponse {
+    pub tokens: Vec<PaymentTokenResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentTokenResponse {
+    pub address: Addr,
+    pub min_price: Option<Uint128>,
+}
+
+// Admin pending acceptance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdminResponse {
+    pub pending_admin: Option<Addr>,
+}
+
+// Pause state of the marketplace
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    pub paused: bool,
+}
+
+// List token ids blocked from listing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockedNamesResponse {
+    pub names: Vec<String>,
+}
+
+// List patterns blocking token ids from listing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockedPatternsResponse {
+    pub patterns: Vec<String>,
+}
+
+// List addresses blocked from creating or taking part in swaps
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockedAddressesResponse {
+    pub addresses: Vec<Addr>,
+}
+
+// Role membership of an address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+// List addresses granted a role
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+// Amount in a cw20 (address) or native denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenAmount {
+    pub payment_token: String,
+    pub amount: Uint128,
+}
+
+// Marketplace aggregates
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    pub sales: u64,
+    pub volume: Vec<TokenAmount>, // Total traded per payment token
+    pub floor: Vec<TokenAmount>,  // Cheapest active listing per accepted payment token
+}
+
+// Most recent sale of a token id
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LastSaleResponse {
+    pub last_sale: Option<LastSale>,
+}
+
+// List trade records (`start_after` is a trade id)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TradesResponse {
+    pub trades: Vec<Trade>,
+}
+
+// A swap and its id
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapEntry {
+    pub id: String,
+    pub swap: CW721Swap,
+}
+
+// Activity of an address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserActivityResponse {
+    pub listings: Vec<SwapEntry>,    // Active sales created by the address
+    pub offers_made: Vec<SwapEntry>, // Active offers created by the address
+    pub offers_received: Vec<SwapEntry>, // Active offers on owned tokens, grouped by payment token
+    pub purchases: Vec<Trade>,
+    pub sales: Vec<Trade>,
+}
+
+// Paginated offers on tokens held by an owner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersReceivedResponse {
+    pub offers: Vec<SwapEntry>,
+    pub page: u32,
+    pub total: u128,
+}
+
+// Best ask and bid of a token in a payment token (cw20 address or native denom)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BestPrice {
+    pub payment_token: String,
+    pub best_ask: Option<SwapEntry>, // Lowest sale
+    pub best_bid: Option<SwapEntry>, // Highest offer
+}
+
+// Best asks and bids of a token, ordered by payment token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BestPricesResponse {
+    pub prices: Vec<BestPrice>,
+}
+
 // Get details about a swap
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct DetailsResponse {
+    pub id: String,
     pub creator: Addr,
     pub contract: Addr,
     pub payment_token: Option<Addr>,
+    pub denom: Option<String>,
     pub token_id: String,
     pub expires: Expiration,
     pub price: Uint128,
     pub swap_type: SwapType,
+    pub revision: u64, // Bumped on every `Update`
+}
+
+// Details of a swap id, `None` if the swap does not exist
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DetailsManyEntry {
+    pub id: String,
+    pub details: Option<DetailsResponse>,
+}
+
+// Get details about many swaps, in the order requested
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DetailsManyResponse {
+    pub swaps: Vec<DetailsManyEntry>,
+}
+
+// Search results, `next` is set when more results follow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SearchResponse {
+    pub swaps: Vec<SwapEntry>,
+    pub next: Option<SearchCursor>,
 }
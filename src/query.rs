@@ -1,16 +1,36 @@
-use cosmwasm_std::{Addr, Deps, Order, StdResult, Uint128};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Deps, Env, Order, StdError, StdResult, Storage, Uint128};
+use cw721::OwnerOfResponse;
+use cw721_base::QueryMsg as Cw721QueryMsg;
 use cw_storage_plus::Bound;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::msg::{DetailsResponse, ListResponse};
-use crate::state::{all_swap_ids, CW721Swap, SwapType, CONFIG, SWAPS};
-use crate::utils::{calculate_page_params, PageParams};
+use crate::msg::{
+    BestPrice, BestPricesResponse, BlockedAddressesResponse, BlockedNamesResponse,
+    BlockedPatternsResponse, DetailsManyEntry, DetailsManyResponse, DetailsResponse,
+    HasRoleResponse, LastSaleResponse, ListResponse, NativeDenomsResponse, OffersReceivedResponse,
+    PauseStatusResponse, PaymentTokenResponse, PaymentTokensResponse, PendingAdminResponse,
+    RoleMembersResponse, SearchCursor, SearchFilter, SearchResponse, SearchSort, StatsResponse,
+    SwapEntry, TokenAmount, TradesResponse, UserActivityResponse,
+};
+use crate::state::{
+    all_swap_ids, has_role, price_index, price_key, CW721Swap, Config, Role, SwapType, Trade,
+    BLOCKED_ADDRESSES, BLOCKED_NAMES, BLOCKED_PATTERNS, CONFIG, LAST_SALES, LISTED_NAMES,
+    LISTING_PRICES, NATIVE_DENOMS, PAUSED, PAYMENT_TOKENS, PENDING_ADMIN, ROLES, SALES_COUNT,
+    SWAPS, SWAPS_BY_CREATOR, SWAPS_BY_TOKEN, SWAP_TOTALS, SWAP_TOTALS_BY_CREATOR,
+    SWAP_TOTALS_BY_DENOM, SWAP_TOTALS_BY_TOKEN, TOKEN_TRADES, TRADES, USER_TRADES, VOLUME,
+};
+use crate::utils::{
+    calculate_page_params, name_length, name_tld, query_owned_tokens, PageParams, MAX_FLOOR_SCAN,
+};
 
 // Pagination query result format for filtered swap queries
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PageResult {
-    pub swaps: Vec<CW721Swap>,
+    pub swaps: Vec<SwapEntry>,
     pub page: u32,
     pub total: u128,
 }
@@ -19,18 +39,55 @@ pub struct PageResult {
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 10;
 
-pub fn query_details(deps: Deps, id: String) -> StdResult<DetailsResponse> {
-    let swap = SWAPS.load(deps.storage, &id)?;
-    let details = DetailsResponse {
+pub fn query_config(deps: Deps) -> StdResult<Config> {
+    CONFIG.load(deps.storage)
+}
+
+fn swap_details(config: &Config, id: String, swap: CW721Swap) -> DetailsResponse {
+    let denom: Option<String> = match swap.payment_token {
+        Some(_) => None,
+        None => Some(swap.native_denom(&config.denom)),
+    };
+    DetailsResponse {
+        id,
         creator: swap.creator,
         contract: swap.nft_contract,
         payment_token: swap.payment_token,
+        denom,
         token_id: swap.token_id,
         expires: swap.expires,
         price: swap.price,
         swap_type: swap.swap_type,
-    };
-    Ok(details)
+        revision: swap.revision,
+    }
+}
+
+pub fn query_details(deps: Deps, id: String) -> StdResult<DetailsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let swap = SWAPS.load(deps.storage, &id)?;
+    Ok(swap_details(&config, id, swap))
+}
+
+pub fn query_details_many(deps: Deps, ids: Vec<String>) -> StdResult<DetailsManyResponse> {
+    if ids.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "At most {} ids can be queried at once",
+            MAX_LIMIT
+        )));
+    }
+    let config = CONFIG.load(deps.storage)?;
+
+    let swaps: StdResult<Vec<DetailsManyEntry>> = ids
+        .into_iter()
+        .map(|id| {
+            let details = SWAPS
+                .may_load(deps.storage, &id)?
+                .map(|swap| swap_details(&config, id.clone(), swap));
+            Ok(DetailsManyEntry { id, details })
+        })
+        .collect();
+
+    Ok(DetailsManyResponse { swaps: swaps? })
 }
 
 pub fn query_list(
@@ -46,20 +103,891 @@ pub fn query_list(
     })
 }
 
-pub fn query_swap_total(deps: Deps, side: SwapType) -> StdResult<u128> {
+pub fn query_native_denoms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<NativeDenomsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+    let denoms: StdResult<Vec<String>> = NATIVE_DENOMS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    Ok(NativeDenomsResponse { denoms: denoms? })
+}
+
+pub fn query_payment_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PaymentTokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after.map(Addr::unchecked);
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let tokens: StdResult<Vec<PaymentTokenResponse>> = PAYMENT_TOKENS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(address, token)| PaymentTokenResponse {
+                address,
+                min_price: token.min_price,
+            })
+        })
+        .collect();
+
+    Ok(PaymentTokensResponse { tokens: tokens? })
+}
+
+pub fn query_pending_admin(deps: Deps) -> StdResult<PendingAdminResponse> {
+    Ok(PendingAdminResponse {
+        pending_admin: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    Ok(PauseStatusResponse {
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or(false),
+    })
+}
+
+pub fn query_blocked_names(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BlockedNamesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+    let names: StdResult<Vec<String>> = BLOCKED_NAMES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    Ok(BlockedNamesResponse { names: names? })
+}
+
+pub fn query_blocked_patterns(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BlockedPatternsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+    let patterns: StdResult<Vec<String>> = BLOCKED_PATTERNS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    Ok(BlockedPatternsResponse {
+        patterns: patterns?,
+    })
+}
+
+pub fn query_blocked_addresses(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BlockedAddressesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after.map(Addr::unchecked);
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let addresses: StdResult<Vec<Addr>> = BLOCKED_ADDRESSES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    Ok(BlockedAddressesResponse {
+        addresses: addresses?,
+    })
+}
+
+pub fn query_has_role(deps: Deps, role: Role, address: String) -> StdResult<HasRoleResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(HasRoleResponse {
+        has_role: has_role(deps.storage, &role, &address),
+    })
+}
+
+pub fn query_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RoleMembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after.map(Addr::unchecked);
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let members: StdResult<Vec<Addr>> = ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect();
+
+    Ok(RoleMembersResponse { members: members? })
+}
+
+pub fn query_stats(deps: Deps, env: Env) -> StdResult<StatsResponse> {
     let config = CONFIG.load(deps.storage)?;
-    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+
+    let volume: StdResult<Vec<TokenAmount>> = VOLUME
         .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(payment_token, amount)| TokenAmount {
+                payment_token,
+                amount,
+            })
+        })
         .collect();
 
-    let results: Vec<CW721Swap> = swaps
-        .unwrap()
-        .into_iter()
-        .map(|t| t.1)
-        .filter(|item| item.nft_contract == config.cw721 && item.swap_type == side)
+    // Floor prices of the default denom and allowlisted payment tokens
+    let mut tokens: Vec<String> = vec![config.denom.clone()];
+    for denom in NATIVE_DENOMS.keys(deps.storage, None, None, Order::Ascending) {
+        tokens.push(denom?);
+    }
+    for address in PAYMENT_TOKENS.keys(deps.storage, None, None, Order::Ascending) {
+        tokens.push(address?.to_string());
+    }
+    let mut floor: Vec<TokenAmount> = vec![];
+    for payment_token in tokens {
+        if let Some(amount) = floor_price(deps, &env, &payment_token, &config.cw721)? {
+            floor.push(TokenAmount {
+                payment_token,
+                amount,
+            });
+        }
+    }
+
+    Ok(StatsResponse {
+        sales: SALES_COUNT.may_load(deps.storage)?.unwrap_or_default(),
+        volume: volume?,
+        floor,
+    })
+}
+
+// Cheapest active listing of the collection in a settlement token, only
+// looking at the `MAX_FLOOR_SCAN` cheapest entries of the price index
+fn floor_price(deps: Deps, env: &Env, token: &str, cw721: &Addr) -> StdResult<Option<Uint128>> {
+    for key in LISTING_PRICES
+        .sub_prefix(token)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(MAX_FLOOR_SCAN)
+    {
+        let (_, id) = key?;
+        let swap = SWAPS.load(deps.storage, &id)?;
+        if swap.nft_contract != *cw721 || swap.is_expired(&env.block) {
+            continue;
+        }
+        // Skip stale listings whose creator no longer owns the token
+        let owner: Option<OwnerOfResponse> = deps
+            .querier
+            .query_wasm_smart(
+                cw721,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: swap.token_id.clone(),
+                    include_expired: None,
+                },
+            )
+            .ok();
+        if owner.is_some_and(|res| res.owner == swap.creator.as_str()) {
+            return Ok(Some(swap.price));
+        }
+    }
+    Ok(None)
+}
+
+pub fn query_last_sale(deps: Deps, token_id: String) -> StdResult<LastSaleResponse> {
+    Ok(LastSaleResponse {
+        last_sale: LAST_SALES.may_load(deps.storage, &token_id)?,
+    })
+}
+
+pub fn query_trade_history(
+    deps: Deps,
+    token_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TradesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    let trades: StdResult<Vec<Trade>> = TOKEN_TRADES
+        .prefix(&token_id)
+        .keys(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|trade_id| TRADES.load(deps.storage, trade_id?))
+        .collect();
+
+    Ok(TradesResponse { trades: trades? })
+}
+
+pub fn query_recent_trades(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TradesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    let trades: StdResult<Vec<Trade>> = TRADES
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, trade)| trade))
         .collect();
 
-    Ok(results.len() as u128)
+    Ok(TradesResponse { trades: trades? })
+}
+
+pub fn query_user_activity(
+    deps: Deps,
+    env: Env,
+    address: String,
+    limit: Option<u32>,
+) -> StdResult<UserActivityResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+
+    let mut listings: Vec<SwapEntry> = vec![];
+    let mut offers_made: Vec<SwapEntry> = vec![];
+    for id in SWAPS_BY_CREATOR
+        .prefix(&address)
+        .keys(deps.storage, None, None, Order::Ascending)
+    {
+        if listings.len() >= limit && offers_made.len() >= limit {
+            break;
+        }
+        let id = id?;
+        let swap = SWAPS.load(deps.storage, &id)?;
+        if swap.is_expired(&env.block) {
+            continue;
+        }
+        let entries = match swap.swap_type {
+            SwapType::Sale => &mut listings,
+            SwapType::Offer => &mut offers_made,
+        };
+        if entries.len() < limit {
+            entries.push(SwapEntry { id, swap });
+        }
+    }
+
+    let mut offers_received = offers_on_owned_tokens(deps, &env, &config, &address, None)?;
+    offers_received.truncate(limit);
+
+    let mut purchases: Vec<Trade> = vec![];
+    let mut sales: Vec<Trade> = vec![];
+    for trade_id in USER_TRADES
+        .prefix(&address)
+        .keys(deps.storage, None, None, Order::Descending)
+    {
+        if purchases.len() >= limit && sales.len() >= limit {
+            break;
+        }
+        let trade = TRADES.load(deps.storage, trade_id?)?;
+        let trades = if trade.buyer == address {
+            &mut purchases
+        } else {
+            &mut sales
+        };
+        if trades.len() < limit {
+            trades.push(trade);
+        }
+    }
+
+    Ok(UserActivityResponse {
+        listings,
+        offers_made,
+        offers_received,
+        purchases,
+        sales,
+    })
+}
+
+// Active offers on all tokens of the collection held by `owner`,
+// highest price first
+// Offers are grouped by payment token, since raw amounts of different
+// tokens are not comparable, then ranked by price within each group
+fn offers_on_owned_tokens(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    owner: &Addr,
+    payment_token: Option<&str>,
+) -> StdResult<Vec<SwapEntry>> {
+    let mut offers: Vec<SwapEntry> = vec![];
+    let mut start_after: Option<String> = None;
+    loop {
+        let tokens = query_owned_tokens(deps, &config.cw721, owner, start_after, Some(MAX_LIMIT))?;
+        for token_id in &tokens {
+            for id in
+                SWAPS_BY_TOKEN
+                    .prefix(token_id)
+                    .keys(deps.storage, None, None, Order::Ascending)
+            {
+                let id = id?;
+                let swap = SWAPS.load(deps.storage, &id)?;
+                if swap.swap_type == SwapType::Offer
+                    && swap.nft_contract == config.cw721
+                    && swap.creator != *owner
+                    && !swap.is_expired(&env.block)
+                    && (payment_token.is_none()
+                        || payment_token == Some(swap.settlement_token(&config.denom).as_str()))
+                {
+                    offers.push(SwapEntry { id, swap });
+                }
+            }
+        }
+        if tokens.len() < MAX_LIMIT as usize {
+            break;
+        }
+        start_after = tokens.last().cloned();
+    }
+    offers.sort_by(|a, b| {
+        a.swap
+            .settlement_token(&config.denom)
+            .cmp(&b.swap.settlement_token(&config.denom))
+            .then(b.swap.price.cmp(&a.swap.price))
+            .then(a.id.cmp(&b.id))
+    });
+    Ok(offers)
+}
+
+pub fn query_offers_received(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    payment_token: Option<String>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<OffersReceivedResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let owner = deps.api.addr_validate(&owner)?;
+    let offers = offers_on_owned_tokens(deps, &env, &config, &owner, payment_token.as_deref())?;
+
+    let paging: PageParams = calculate_page_params(page, limit, offers.len() as u32)?;
+    Ok(OffersReceivedResponse {
+        offers: offers[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    })
+}
+
+pub fn query_best_prices(deps: Deps, env: Env, token_id: String) -> StdResult<BestPricesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let owner: Option<Addr> = deps
+        .querier
+        .query_wasm_smart::<OwnerOfResponse>(
+            &config.cw721,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.clone(),
+                include_expired: None,
+            },
+        )
+        .ok()
+        .map(|res| Addr::unchecked(res.owner));
+
+    let mut prices: BTreeMap<String, BestPrice> = BTreeMap::new();
+    for id in SWAPS_BY_TOKEN
+        .prefix(&token_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+    {
+        let id = id?;
+        let swap = SWAPS.load(deps.storage, &id)?;
+        if swap.nft_contract != config.cw721
+            || swap.is_expired(&env.block)
+            || BLOCKED_ADDRESSES.has(deps.storage, &swap.creator)
+        {
+            continue;
+        }
+        // Sales must be created by the token owner, offers by anyone else
+        let creator_owns = owner.as_ref() == Some(&swap.creator);
+        let payment_token = swap.settlement_token(&config.denom);
+        let best = prices
+            .entry(payment_token.clone())
+            .or_insert_with(|| BestPrice {
+                payment_token,
+                best_ask: None,
+                best_bid: None,
+            });
+        match swap.swap_type {
+            SwapType::Sale
+                if creator_owns
+                    && best
+                        .best_ask
+                        .as_ref()
+                        .map_or(true, |ask| swap.price < ask.swap.price) =>
+            {
+                best.best_ask = Some(SwapEntry { id, swap });
+            }
+            SwapType::Offer
+                if !creator_owns
+                    && best
+                        .best_bid
+                        .as_ref()
+                        .map_or(true, |bid| swap.price > bid.swap.price) =>
+            {
+                best.best_bid = Some(SwapEntry { id, swap });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(BestPricesResponse {
+        prices: prices
+            .into_values()
+            .filter(|best| best.best_ask.is_some() || best.best_bid.is_some())
+            .collect(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_swap_total(
+    deps: Deps,
+    env: Env,
+    side: SwapType,
+    denom: Option<String>,
+    creator: Option<String>,
+    collection: Option<String>,
+    token_id: Option<String>,
+    include_expired: Option<bool>,
+) -> StdResult<u128> {
+    let config = CONFIG.load(deps.storage)?;
+    let collection = match collection {
+        Some(collection) => deps.api.addr_validate(&collection)?,
+        None => config.cw721.clone(),
+    };
+    let creator = creator
+        .map(|creator| deps.api.addr_validate(&creator))
+        .transpose()?;
+    let include_expired = include_expired.unwrap_or(true);
+
+    // Maintained counts cover a single filter, including expired swaps
+    let filters = [denom.is_some(), creator.is_some(), token_id.is_some()]
+        .iter()
+        .filter(|filter| **filter)
+        .count();
+    if include_expired && filters <= 1 {
+        let side = side.as_str();
+        let total = if let Some(denom) = &denom {
+            SWAP_TOTALS_BY_DENOM.may_load(deps.storage, (&collection, side, denom))?
+        } else if let Some(creator) = &creator {
+            SWAP_TOTALS_BY_CREATOR.may_load(deps.storage, (&collection, side, creator))?
+        } else if let Some(token_id) = &token_id {
+            SWAP_TOTALS_BY_TOKEN.may_load(deps.storage, (&collection, side, token_id))?
+        } else {
+            SWAP_TOTALS.may_load(deps.storage, (&collection, side))?
+        };
+        return Ok(total.unwrap_or_default() as u128);
+    }
+
+    // Otherwise scan the most selective index
+    let ids: Vec<String> = if let Some(token_id) = &token_id {
+        SWAPS_BY_TOKEN
+            .prefix(token_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?
+    } else if let Some(creator) = &creator {
+        SWAPS_BY_CREATOR
+            .prefix(creator)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?
+    } else {
+        SWAPS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?
+    };
+    let mut total: u128 = 0;
+    for id in ids {
+        let swap = SWAPS.load(deps.storage, &id)?;
+        let matches = swap.nft_contract == collection
+            && swap.swap_type == side
+            && (include_expired || !swap.is_expired(&env.block))
+            && denom
+                .as_ref()
+                .map_or(true, |denom| swap.settlement_token(&config.denom) == *denom)
+            && creator
+                .as_ref()
+                .map_or(true, |creator| swap.creator == *creator)
+            && token_id
+                .as_ref()
+                .map_or(true, |token_id| swap.token_id == *token_id);
+        if matches {
+            total += 1;
+        }
+    }
+    Ok(total)
+}
+
+fn search_cursor(entry: &SwapEntry) -> SearchCursor {
+    SearchCursor {
+        id: entry.id.clone(),
+        price: entry.swap.price,
+        token_id: entry.swap.token_id.clone(),
+    }
+}
+
+// Orders by the sort key, then by swap id (descending when sorting by
+// descending price, so the order is the reverse of ascending price)
+fn search_order(sort: SearchSort, a: &SearchCursor, b: &SearchCursor) -> Ordering {
+    match sort {
+        SearchSort::Id => a.id.cmp(&b.id),
+        SearchSort::PriceAscending => a.price.cmp(&b.price).then_with(|| a.id.cmp(&b.id)),
+        SearchSort::PriceDescending => b.price.cmp(&a.price).then_with(|| b.id.cmp(&a.id)),
+        SearchSort::Name => a.token_id.cmp(&b.token_id).then_with(|| a.id.cmp(&b.id)),
+    }
+}
+
+// Index a `Search` draws its candidate swaps from
+enum SearchIndex {
+    // All swaps, by id
+    All,
+    // Swaps of a creator, by id
+    Creator(Addr),
+    // Swaps of a type in a payment token, by price
+    Price(SwapType, String),
+    // Swaps of names starting with a prefix, by name
+    Names(String),
+}
+
+impl SearchIndex {
+    // Whether the index yields candidates in `sort` order, so a page
+    // can resume from the cursor and stop once it is full
+    fn is_sorted_by(&self, sort: SearchSort) -> bool {
+        matches!(
+            (self, sort),
+            (SearchIndex::All | SearchIndex::Creator(_), SearchSort::Id)
+                | (
+                    SearchIndex::Price(..),
+                    SearchSort::PriceAscending | SearchSort::PriceDescending
+                )
+                | (SearchIndex::Names(_), SearchSort::Name)
+        )
+    }
+
+    // Candidate swap ids in index order, starting after the cursor when
+    // the index is sorted like the results
+    fn candidates<'a>(
+        &self,
+        storage: &'a dyn Storage,
+        filter: &SearchFilter,
+        sort: SearchSort,
+        start_after: Option<&SearchCursor>,
+    ) -> Box<dyn Iterator<Item = StdResult<String>> + 'a> {
+        let cursor = start_after.filter(|_| self.is_sorted_by(sort));
+        match self {
+            SearchIndex::All => {
+                let min = cursor.map(|cursor| Bound::exclusive(cursor.id.as_str()));
+                SWAPS.keys(storage, min, None, Order::Ascending)
+            }
+            SearchIndex::Creator(creator) => {
+                let min = cursor.map(|cursor| Bound::exclusive(cursor.id.as_str()));
+                SWAPS_BY_CREATOR
+                    .prefix(creator)
+                    .keys(storage, min, None, Order::Ascending)
+            }
+            SearchIndex::Price(side, denom) => {
+                let mut min = filter
+                    .min_price
+                    .map(|min| Bound::inclusive((price_key(min), "")));
+                let mut max = filter
+                    .max_price
+                    .and_then(|max| max.u128().checked_add(1))
+                    .map(|max| Bound::exclusive((price_key(max.into()), "")));
+                let order = if sort == SearchSort::PriceDescending {
+                    if let Some(cursor) = cursor {
+                        max = Some(Bound::exclusive((
+                            price_key(cursor.price),
+                            cursor.id.as_str(),
+                        )));
+                    }
+                    Order::Descending
+                } else {
+                    if let Some(cursor) = cursor {
+                        min = Some(Bound::exclusive((
+                            price_key(cursor.price),
+                            cursor.id.as_str(),
+                        )));
+                    }
+                    Order::Ascending
+                };
+                Box::new(
+                    price_index(side)
+                        .sub_prefix(denom)
+                        .keys(storage, min, max, order)
+                        .map(|key| key.map(|(_, id)| id)),
+                )
+            }
+            SearchIndex::Names(prefix) => {
+                // Names are visited in key order, from the prefix or the
+                // cursor's name, whichever comes later
+                let prefix = prefix.clone();
+                let cursor = cursor.map(|cursor| (cursor.token_id.clone(), cursor.id.clone()));
+                let start = match &cursor {
+                    Some((name, _)) if *name > prefix => name.clone(),
+                    _ => prefix.clone(),
+                };
+                Box::new(
+                    LISTED_NAMES
+                        .keys(
+                            storage,
+                            Some(Bound::inclusive(start.as_str())),
+                            None,
+                            Order::Ascending,
+                        )
+                        .take_while(move |name| match name {
+                            Ok(name) => name.starts_with(prefix.as_str()),
+                            Err(_) => true,
+                        })
+                        .flat_map(move |name| match name {
+                            Ok(name) => {
+                                // Skip swaps of the cursor's name up to the cursor
+                                let min = cursor
+                                    .as_ref()
+                                    .filter(|(cursor_name, _)| *cursor_name == name)
+                                    .map(|(_, id)| Bound::exclusive(id.as_str()));
+                                SWAPS_BY_TOKEN.prefix(&name).keys(
+                                    storage,
+                                    min,
+                                    None,
+                                    Order::Ascending,
+                                )
+                            }
+                            Err(err) => Box::new(std::iter::once(Err(err))),
+                        }),
+                )
+            }
+        }
+    }
+}
+
+// Expirations only compare within a kind (block height or time): a swap
+// never matches expiry filters of the other kind, while swaps that never
+// expire are after any bound
+fn search_matches(
+    swap: &CW721Swap,
+    filter: &SearchFilter,
+    collection: &Addr,
+    creator: Option<&Addr>,
+    default_denom: &str,
+    env: &Env,
+) -> bool {
+    let name_length = name_length(&swap.token_id);
+    swap.nft_contract == *collection
+        && (filter.include_expired.unwrap_or(false) || !swap.is_expired(&env.block))
+        && filter
+            .swap_type
+            .as_ref()
+            .map_or(true, |side| swap.swap_type == *side)
+        && creator.map_or(true, |creator| swap.creator == *creator)
+        && filter
+            .denom
+            .as_ref()
+            .map_or(true, |denom| swap.settlement_token(default_denom) == *denom)
+        && filter.min_price.map_or(true, |min| swap.price >= min)
+        && filter.max_price.map_or(true, |max| swap.price <= max)
+        && filter
+            .expires_after
+            .map_or(true, |after| swap.expires >= after)
+        && filter
+            .expires_before
+            .map_or(true, |before| swap.expires <= before)
+        && filter
+            .min_name_length
+            .map_or(true, |min| name_length >= min)
+        && filter
+            .max_name_length
+            .map_or(true, |max| name_length <= max)
+        && filter
+            .name_prefix
+            .as_ref()
+            .map_or(true, |prefix| swap.token_id.starts_with(prefix.as_str()))
+}
+
+pub fn query_search(
+    deps: Deps,
+    env: Env,
+    filter: SearchFilter,
+    sort: Option<SearchSort>,
+    start_after: Option<SearchCursor>,
+    limit: Option<u32>,
+) -> StdResult<SearchResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let sort = sort.unwrap_or(SearchSort::Id);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let collection = match &filter.collection {
+        Some(collection) => deps.api.addr_validate(collection)?,
+        None => config.cw721.clone(),
+    };
+    let creator = filter
+        .creator
+        .as_ref()
+        .map(|creator| deps.api.addr_validate(creator))
+        .transpose()?;
+    // Bounds of different kinds would never match together
+    if let (Some(after), Some(before)) = (filter.expires_after, filter.expires_before) {
+        if after.partial_cmp(&before).is_none() {
+            return Err(StdError::generic_err(
+                "expires_after and expires_before must both be heights or both be times",
+            ));
+        }
+    }
+
+    // Candidates come from whichever applicable index holds the fewest
+    // of them: a creator's swaps, swaps of a type and payment token within
+    // the price range, swaps of names with the prefix, or all swaps
+    let mut indexes: Vec<SearchIndex> = vec![];
+    if let Some(creator) = &creator {
+        indexes.push(SearchIndex::Creator(creator.clone()));
+    }
+    if let (Some(side), Some(denom)) = (&filter.swap_type, &filter.denom) {
+        indexes.push(SearchIndex::Price(side.clone(), denom.clone()));
+    }
+    if let Some(prefix) = &filter.name_prefix {
+        indexes.push(SearchIndex::Names(prefix.clone()));
+    }
+    let mut index = SearchIndex::All;
+    let mut fewest: Option<usize> = None;
+    for candidate in indexes {
+        // Counting stops once it can't beat the best index so far
+        let count = candidate
+            .candidates(deps.storage, &filter, sort, start_after.as_ref())
+            .take(fewest.unwrap_or(usize::MAX))
+            .count();
+        if fewest.map_or(true, |fewest| count < fewest) {
+            fewest = Some(count);
+            index = candidate;
+        }
+    }
+
+    // A sorted index stops at the first match past the page, otherwise
+    // matches after the cursor are collected and sorted
+    let sorted = index.is_sorted_by(sort);
+    let mut swaps: Vec<SwapEntry> = vec![];
+    for id in index.candidates(deps.storage, &filter, sort, start_after.as_ref()) {
+        let id = id?;
+        let swap = SWAPS.load(deps.storage, &id)?;
+        if !search_matches(
+            &swap,
+            &filter,
+            &collection,
+            creator.as_ref(),
+            &config.denom,
+            &env,
+        ) {
+            continue;
+        }
+        let entry = SwapEntry { id, swap };
+        if !sorted
+            && start_after
+                .as_ref()
+                .is_some_and(|cursor| search_order(sort, &search_cursor(&entry), cursor).is_le())
+        {
+            continue;
+        }
+        swaps.push(entry);
+        if sorted && swaps.len() > limit {
+            break;
+        }
+    }
+    if !sorted {
+        swaps.sort_by(|a, b| search_order(sort, &search_cursor(a), &search_cursor(b)));
+    }
+
+    let next = if swaps.len() > limit {
+        swaps.truncate(limit);
+        swaps.last().map(search_cursor)
+    } else {
+        None
+    };
+    Ok(SearchResponse { swaps, next })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_search_names(
+    deps: Deps,
+    env: Env,
+    prefix: Option<String>,
+    contains: Option<String>,
+    tld: Option<String>,
+    min_length: Option<u32>,
+    max_length: Option<u32>,
+    start_after: Option<SearchCursor>,
+    limit: Option<u32>,
+) -> StdResult<SearchResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tld = tld.map(|tld| tld.trim_start_matches('.').to_string());
+
+    // Names are visited in key order, from the prefix or the cursor's name,
+    // whichever comes later
+    let start = [
+        prefix.as_deref(),
+        start_after.as_ref().map(|cursor| cursor.token_id.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .max();
+
+    let mut swaps: Vec<SwapEntry> = vec![];
+    let mut next: Option<SearchCursor> = None;
+    'names: for name in LISTED_NAMES.keys(
+        deps.storage,
+        start.map(Bound::inclusive),
+        None,
+        Order::Ascending,
+    ) {
+        let name = name?;
+        if prefix
+            .as_ref()
+            .is_some_and(|prefix| !name.starts_with(prefix.as_str()))
+        {
+            break;
+        }
+        let name_length = name_length(&name);
+        let matches = contains
+            .as_ref()
+            .map_or(true, |contains| name.contains(contains.as_str()))
+            && tld
+                .as_ref()
+                .map_or(true, |tld| name_tld(&name) == Some(tld))
+            && min_length.map_or(true, |min| name_length >= min)
+            && max_length.map_or(true, |max| name_length <= max);
+        if !matches {
+            continue;
+        }
+
+        // Skip swaps of the cursor's name up to the cursor
+        let min = start_after
+            .as_ref()
+            .filter(|cursor| cursor.token_id == name)
+            .map(|cursor| Bound::exclusive(cursor.id.as_str()));
+        for id in SWAPS_BY_TOKEN
+            .prefix(&name)
+            .keys(deps.storage, min, None, Order::Ascending)
+        {
+            let id = id?;
+            let swap = SWAPS.load(deps.storage, &id)?;
+            if swap.nft_contract != config.cw721
+                || swap.swap_type != SwapType::Sale
+                || swap.is_expired(&env.block)
+            {
+                continue;
+            }
+            if swaps.len() == limit {
+                next = swaps.last().map(search_cursor);
+                break 'names;
+            }
+            swaps.push(SwapEntry { id, swap });
+        }
+    }
+
+    Ok(SearchResponse { swaps, next })
 }
 
 pub fn query_swaps(
@@ -73,11 +1001,11 @@ pub fn query_swaps(
         .range(deps.storage, None, None, Order::Ascending)
         .collect();
 
-    let results: Vec<CW721Swap> = swaps
+    let results: Vec<SwapEntry> = swaps
         .unwrap()
         .into_iter()
-        .map(|t| t.1)
-        .filter(|item| item.nft_contract == config.cw721 && item.swap_type == side)
+        .map(|(id, swap)| SwapEntry { id, swap })
+        .filter(|item| item.swap.nft_contract == config.cw721 && item.swap.swap_type == side)
         .collect();
 
     let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
@@ -102,23 +1030,23 @@ pub fn query_swaps_of_token(
         .range(deps.storage, None, None, Order::Ascending)
         .collect();
 
-    let results: Vec<CW721Swap> = if let Some(swap_type) = side {
+    let results: Vec<SwapEntry> = if let Some(swap_type) = side {
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.token_id == token_id
-                    && item.swap_type == swap_type
+                item.swap.nft_contract == config.cw721
+                    && item.swap.token_id == token_id
+                    && item.swap.swap_type == swap_type
             })
             .collect()
     } else {
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
-            .filter(|item| item.nft_contract == config.cw721 && item.token_id == token_id)
+            .map(|(id, swap)| SwapEntry { id, swap })
+            .filter(|item| item.swap.nft_contract == config.cw721 && item.swap.token_id == token_id)
             .collect()
     };
 
@@ -145,12 +1073,14 @@ pub fn query_swaps_by_creator(
         .range(deps.storage, None, None, Order::Ascending)
         .collect();
 
-    let results: Vec<CW721Swap> = swaps
+    let results: Vec<SwapEntry> = swaps
         .unwrap()
         .into_iter()
-        .map(|t| t.1)
+        .map(|(id, swap)| SwapEntry { id, swap })
         .filter(|item| {
-            item.nft_contract == config.cw721 && item.creator == address && item.swap_type == side
+            item.swap.nft_contract == config.cw721
+                && item.swap.creator == address
+                && item.swap.swap_type == side
         })
         .collect();
 
@@ -180,27 +1110,27 @@ pub fn query_swaps_by_price(
         .collect();
 
     // With Max range filter
-    let results: Vec<CW721Swap> = if let Some(max_value) = max {
+    let results: Vec<SwapEntry> = if let Some(max_value) = max {
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.price.u128() >= min.u128()
-                    && item.price.u128() <= max_value.u128()
-                    && item.swap_type == side
+                item.swap.nft_contract == config.cw721
+                    && item.swap.price.u128() >= min.u128()
+                    && item.swap.price.u128() <= max_value.u128()
+                    && item.swap.swap_type == side
             })
             .collect()
     } else {
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.price.u128() >= min.u128()
-                    && item.swap_type == side
+                item.swap.nft_contract == config.cw721
+                    && item.swap.price.u128() >= min.u128()
+                    && item.swap.swap_type == side
             })
             .collect()
     };
@@ -218,6 +1148,7 @@ pub fn query_swaps_by_price(
 pub fn query_swaps_by_denom(
     deps: Deps,
     payment_token: Option<Addr>,
+    denom: Option<String>,
     swap_type: Option<SwapType>,
     page: Option<u32>,
     limit: Option<u32>,
@@ -229,27 +1160,29 @@ pub fn query_swaps_by_denom(
         .collect();
 
     // Requested cw20 denom
-    let results: Vec<CW721Swap> = if let Some(token_addr) = payment_token {
+    let results: Vec<SwapEntry> = if let Some(token_addr) = payment_token {
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.payment_token.clone().unwrap() == token_addr
-                    && item.swap_type == side
+                item.swap.nft_contract == config.cw721
+                    && item.swap.payment_token.as_ref() == Some(&token_addr)
+                    && item.swap.swap_type == side
             })
             .collect()
-    // Native ARCH denom
+    // Native denom (defaults to the configured native denom)
     } else {
+        let native_denom = denom.unwrap_or_else(|| config.denom.clone());
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.payment_token.is_none()
-                    && item.swap_type == side
+                item.swap.nft_contract == config.cw721
+                    && item.swap.payment_token.is_none()
+                    && item.swap.native_denom(&config.denom) == native_denom
+                    && item.swap.swap_type == side
             })
             .collect()
     };
@@ -278,15 +1211,15 @@ pub fn query_swaps_by_payment_type(
         .collect();
 
     // cw20 swap
-    let results: Vec<CW721Swap> = if cw20 {
+    let results: Vec<SwapEntry> = if cw20 {
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.payment_token.is_some()
-                    && item.swap_type == side
+                item.swap.nft_contract == config.cw721
+                    && item.swap.payment_token.is_some()
+                    && item.swap.swap_type == side
             })
             .collect()
     // ARCH swap
@@ -294,11 +1227,11 @@ pub fn query_swaps_by_payment_type(
         swaps
             .unwrap()
             .into_iter()
-            .map(|t| t.1)
+            .map(|(id, swap)| SwapEntry { id, swap })
             .filter(|item| {
-                item.nft_contract == config.cw721
-                    && item.payment_token.is_none()
-                    && item.swap_type == side
+                item.swap.nft_contract == config.cw721
+                    && item.swap.payment_token.is_none()
+                    && item.swap.swap_type == side
             })
             .collect()
     };
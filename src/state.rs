@@ -1,13 +1,27 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, BlockInfo, Order, StdResult, Storage, Uint128};
-use cw_storage_plus::{Bound, Item, Map};
+use cosmwasm_std::{Addr, BlockInfo, Empty, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Item, Map, PrimaryKey};
 
 use cw20::Expiration;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Config {
+    pub admin: Option<Addr>, // `None` once the admin has been renounced
+    pub cw721: Addr,
+    pub denom: String, // Default native denom (e.g. `aarch` on mainnet, `aconst` on testnet)
+    #[serde(default)]
+    pub fee_bps: u64, // Fee retained from each sale, in basis points (e.g. 250 = 2.5%)
+    #[serde(default)]
+    pub treasury: Option<Addr>, // Fee recipient; no fees are retained if `None`
+    #[serde(default)]
+    pub update_delay: u64, // Blocks after an `Update` before the swap can be finished
+}
+
+// Config as stored before the native denom became configurable
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LegacyConfig {
     pub admin: Addr,
     pub cw721: Addr,
 }
@@ -16,16 +30,86 @@ pub enum SwapType {
     Offer,
     Sale,
 }
+
+impl SwapType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwapType::Offer => "offer",
+            SwapType::Sale => "sale",
+        }
+    }
+}
+// Roles granted by the admin, in addition to the admin's own rights
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub enum Role {
+    Moderator, // Can delist abusive listings
+    Operator,  // Can change fees
+    Pauser,    // Can pause (but not unpause) trading
+    Guardian,  // Can pause and unpause trading
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Moderator => "moderator",
+            Role::Operator => "operator",
+            Role::Pauser => "pauser",
+            Role::Guardian => "guardian",
+        }
+    }
+}
+
 // swap type of false equals offer, swap type of true equals buy
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CW721Swap {
     pub creator: Addr,
     pub nft_contract: Addr,
     pub payment_token: Option<Addr>,
+    // Native denom of the swap; `None` for cw20 swaps
+    // (and for native swaps created before multi-denom support)
+    #[serde(default)]
+    pub denom: Option<String>,
     pub token_id: String,
     pub expires: Expiration,
     pub price: Uint128,
     pub swap_type: SwapType,
+    // Bumped on every `Update`, so takers can pin the terms they saw
+    #[serde(default)]
+    pub revision: u64,
+    // Block height of the last `Update`
+    #[serde(default)]
+    pub updated_at: Option<u64>,
+}
+
+// Most recent sale of a token id
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LastSale {
+    pub swap_id: String,
+    pub price: Uint128,
+    pub payment_token: String, // cw20 address or native denom
+    pub height: u64,
+}
+
+// Record of a finished swap, kept after the swap itself is removed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trade {
+    pub id: u64,
+    pub swap_id: String,
+    pub token_id: String,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub price: Uint128,
+    pub payment_token: String, // cw20 address or native denom
+    pub swap_type: SwapType,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+// Allowlisted cw20 payment token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PaymentToken {
+    // Optional minimum swap price in this token
+    pub min_price: Option<Uint128>,
 }
 
 pub fn all_swap_ids<'a>(
@@ -43,7 +127,283 @@ impl CW721Swap {
     pub fn is_expired(&self, block: &BlockInfo) -> bool {
         self.expires.is_expired(block)
     }
+
+    // Native denom the swap settles in, falling back to `default_denom`
+    // for swaps created before multi-denom support
+    pub fn native_denom(&self, default_denom: &str) -> String {
+        self.denom
+            .clone()
+            .unwrap_or_else(|| default_denom.to_string())
+    }
+
+    // cw20 address or native denom the swap settles in
+    pub fn settlement_token(&self, default_denom: &str) -> String {
+        match &self.payment_token {
+            Some(token) => token.to_string(),
+            None => self.native_denom(default_denom),
+        }
+    }
 }
 
 pub const SWAPS: Map<&str, CW721Swap> = Map::new("cw721_swap");
 pub const CONFIG: Item<Config> = Item::new("config");
+// Last auto-generated swap id
+pub const SWAP_COUNT: Item<u64> = Item::new("swap_count");
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+// Admin proposed by the current admin, pending acceptance
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin");
+// Trading is halted while paused (`Cancel` still works)
+pub const PAUSED: Item<bool> = Item::new("paused");
+// Token ids that cannot be listed (e.g. flagged for phishing or impersonation)
+pub const BLOCKED_NAMES: Map<&str, Empty> = Map::new("blocked_names");
+// Lowercase patterns; token ids containing any of them cannot be listed
+pub const BLOCKED_PATTERNS: Map<&str, Empty> = Map::new("blocked_patterns");
+
+// Addresses that cannot create or take part in swaps (e.g. sanctioned accounts)
+pub const BLOCKED_ADDRESSES: Map<&Addr, Empty> = Map::new("blocked_addresses");
+
+// Role members, keyed by (role, address)
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+// Sale listings keyed by (settlement token, big-endian price, swap id),
+// so the cheapest listing of each token is found without a full scan
+pub const LISTING_PRICES: Map<(&str, Vec<u8>, &str), Empty> = Map::new("listing_prices");
+// Offers keyed the same way as `LISTING_PRICES`
+pub const OFFER_PRICES: Map<(&str, Vec<u8>, &str), Empty> = Map::new("offer_prices");
+// Swap ids of each creator
+pub const SWAPS_BY_CREATOR: Map<(&Addr, &str), Empty> = Map::new("swaps_by_creator");
+// Swap ids of each token id
+pub const SWAPS_BY_TOKEN: Map<(&str, &str), Empty> = Map::new("swaps_by_token");
+// Token ids with at least one swap; single keys sort by name,
+// unlike the length-prefixed token ids of `SWAPS_BY_TOKEN`
+pub const LISTED_NAMES: Map<&str, Empty> = Map::new("listed_names");
+// Swap counts by (collection, swap type), optionally narrowed
+// by settlement token, creator or token id
+pub const SWAP_TOTALS: Map<(&Addr, &str), u64> = Map::new("swap_totals");
+pub const SWAP_TOTALS_BY_DENOM: Map<(&Addr, &str, &str), u64> = Map::new("swap_totals_by_denom");
+pub const SWAP_TOTALS_BY_CREATOR: Map<(&Addr, &str, &Addr), u64> =
+    Map::new("swap_totals_by_creator");
+pub const SWAP_TOTALS_BY_TOKEN: Map<(&Addr, &str, &str), u64> = Map::new("swap_totals_by_token");
+// Number of finished swaps
+pub const SALES_COUNT: Item<u64> = Item::new("sales_count");
+// Total traded per settlement token (cw20 address or native denom)
+pub const VOLUME: Map<&str, Uint128> = Map::new("volume");
+// Most recent sale per token id
+pub const LAST_SALES: Map<&str, LastSale> = Map::new("last_sales");
+// Trade records by trade id, in settlement order
+pub const TRADES: Map<u64, Trade> = Map::new("trades");
+// Trade ids of each token id
+pub const TOKEN_TRADES: Map<(&str, u64), Empty> = Map::new("token_trades");
+// Trade ids of each seller and buyer
+pub const USER_TRADES: Map<(&Addr, u64), Empty> = Map::new("user_trades");
+
+// Allowlist of native denoms (e.g. IBC or token factory denoms)
+// accepted as payment in addition to the default native denom
+pub const NATIVE_DENOMS: Map<&str, Empty> = Map::new("native_denoms");
+
+// Allowlist of cw20 contracts accepted as payment
+pub const PAYMENT_TOKENS: Map<&Addr, PaymentToken> = Map::new("payment_tokens");
+
+pub fn is_native_denom_allowed(storage: &dyn Storage, denom: &str, default_denom: &str) -> bool {
+    denom == default_denom || NATIVE_DENOMS.has(storage, denom)
+}
+
+pub fn has_role(storage: &dyn Storage, role: &Role, address: &Addr) -> bool {
+    ROLES.has(storage, (role.as_str(), address))
+}
+
+pub fn is_name_blocked(storage: &dyn Storage, token_id: &str) -> StdResult<bool> {
+    if BLOCKED_NAMES.has(storage, token_id) {
+        return Ok(true);
+    }
+    let token_id = token_id.to_lowercase();
+    for pattern in BLOCKED_PATTERNS.keys(storage, None, None, Order::Ascending) {
+        if token_id.contains(&pattern?) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Next auto-generated swap id, skipping ids already taken
+pub fn next_swap_id(storage: &mut dyn Storage) -> StdResult<String> {
+    let mut count = SWAP_COUNT.may_load(storage)?.unwrap_or_default();
+    loop {
+        count += 1;
+        let id = count.to_string();
+        if !SWAPS.has(storage, &id) {
+            SWAP_COUNT.save(storage, &count)?;
+            return Ok(id);
+        }
+    }
+}
+
+// Secondary indexes must be updated wherever a swap is saved or removed
+// Price index of listings or offers
+pub fn price_index<'a>(side: &SwapType) -> Map<'static, (&'a str, Vec<u8>, &'a str), Empty> {
+    match side {
+        SwapType::Sale => LISTING_PRICES,
+        SwapType::Offer => OFFER_PRICES,
+    }
+}
+
+// Big-endian price bytes, ordered like the prices themselves
+pub fn price_key(price: Uint128) -> Vec<u8> {
+    price.u128().to_be_bytes().to_vec()
+}
+
+pub fn index_swap(
+    storage: &mut dyn Storage,
+    id: &str,
+    swap: &CW721Swap,
+    default_denom: &str,
+) -> StdResult<()> {
+    SWAPS_BY_CREATOR.save(storage, (&swap.creator, id), &Empty {})?;
+    SWAPS_BY_TOKEN.save(storage, (&swap.token_id, id), &Empty {})?;
+    LISTED_NAMES.save(storage, &swap.token_id, &Empty {})?;
+    update_swap_totals(storage, swap, default_denom, true)?;
+    let token = swap.settlement_token(default_denom);
+    price_index(&swap.swap_type).save(storage, (&token, price_key(swap.price), id), &Empty {})?;
+    Ok(())
+}
+
+pub fn unindex_swap(
+    storage: &mut dyn Storage,
+    id: &str,
+    swap: &CW721Swap,
+    default_denom: &str,
+) -> StdResult<()> {
+    SWAPS_BY_CREATOR.remove(storage, (&swap.creator, id));
+    SWAPS_BY_TOKEN.remove(storage, (&swap.token_id, id));
+    let listed = SWAPS_BY_TOKEN
+        .prefix(&swap.token_id)
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if !listed {
+        LISTED_NAMES.remove(storage, &swap.token_id);
+    }
+    update_swap_totals(storage, swap, default_denom, false)?;
+    let token = swap.settlement_token(default_denom);
+    price_index(&swap.swap_type).remove(storage, (&token, price_key(swap.price), id));
+    Ok(())
+}
+
+fn update_total<'a, K: PrimaryKey<'a>>(
+    storage: &mut dyn Storage,
+    totals: &Map<'a, K, u64>,
+    key: K,
+    increment: bool,
+) -> StdResult<()> {
+    totals.update(storage, key, |total| -> StdResult<u64> {
+        let total = total.unwrap_or_default();
+        Ok(if increment {
+            total + 1
+        } else {
+            total.saturating_sub(1)
+        })
+    })?;
+    Ok(())
+}
+
+fn update_swap_totals(
+    storage: &mut dyn Storage,
+    swap: &CW721Swap,
+    default_denom: &str,
+    increment: bool,
+) -> StdResult<()> {
+    let collection = &swap.nft_contract;
+    let side = swap.swap_type.as_str();
+    let token = swap.settlement_token(default_denom);
+    update_total(storage, &SWAP_TOTALS, (collection, side), increment)?;
+    update_total(
+        storage,
+        &SWAP_TOTALS_BY_DENOM,
+        (collection, side, &token),
+        increment,
+    )?;
+    update_total(
+        storage,
+        &SWAP_TOTALS_BY_CREATOR,
+        (collection, side, &swap.creator),
+        increment,
+    )?;
+    update_total(
+        storage,
+        &SWAP_TOTALS_BY_TOKEN,
+        (collection, side, &swap.token_id),
+        increment,
+    )
+}
+
+// Drop all swap counts, before they are rebuilt by re-indexing every swap
+pub fn clear_swap_totals(storage: &mut dyn Storage) -> StdResult<()> {
+    let totals: Vec<(Addr, String)> = SWAP_TOTALS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (collection, side) in totals {
+        SWAP_TOTALS.remove(storage, (&collection, &side));
+    }
+    let totals: Vec<(Addr, String, String)> = SWAP_TOTALS_BY_DENOM
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (collection, side, token) in totals {
+        SWAP_TOTALS_BY_DENOM.remove(storage, (&collection, &side, &token));
+    }
+    let totals: Vec<(Addr, String, Addr)> = SWAP_TOTALS_BY_CREATOR
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (collection, side, creator) in totals {
+        SWAP_TOTALS_BY_CREATOR.remove(storage, (&collection, &side, &creator));
+    }
+    let totals: Vec<(Addr, String, String)> = SWAP_TOTALS_BY_TOKEN
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (collection, side, token_id) in totals {
+        SWAP_TOTALS_BY_TOKEN.remove(storage, (&collection, &side, &token_id));
+    }
+    Ok(())
+}
+
+pub fn record_sale(
+    storage: &mut dyn Storage,
+    id: &str,
+    swap: &CW721Swap,
+    default_denom: &str,
+    seller: &Addr,
+    buyer: &Addr,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    let payment_token = swap.settlement_token(default_denom);
+    let count = SALES_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    SALES_COUNT.save(storage, &count)?;
+    // The sales count doubles as the trade id
+    let trade = Trade {
+        id: count,
+        swap_id: id.to_string(),
+        token_id: swap.token_id.clone(),
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        price: swap.price,
+        payment_token: payment_token.clone(),
+        swap_type: swap.swap_type.clone(),
+        height: block.height,
+        time: block.time,
+    };
+    TRADES.save(storage, count, &trade)?;
+    TOKEN_TRADES.save(storage, (&swap.token_id, count), &Empty {})?;
+    USER_TRADES.save(storage, (seller, count), &Empty {})?;
+    USER_TRADES.save(storage, (buyer, count), &Empty {})?;
+    VOLUME.update(storage, &payment_token, |volume| -> StdResult<Uint128> {
+        Ok(volume.unwrap_or_default() + swap.price)
+    })?;
+    LAST_SALES.save(
+        storage,
+        &swap.token_id,
+        &LastSale {
+            swap_id: id.to_string(),
+            price: swap.price,
+            payment_token,
+            height: block.height,
+        },
+    )
+}
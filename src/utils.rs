@@ -1,23 +1,35 @@
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg, DepsMut,
-    Env, QueryRequest, StdError, StdResult, WasmMsg, WasmQuery,
+    from_binary, to_binary, Addr, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg, Deps,
+    DepsMut, Env, QueryRequest, StdError, StdResult, Storage, Uint128, WasmMsg, WasmQuery,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw20::Cw20ExecuteMsg;
-use cw721::OwnerOfResponse;
+use cw20::{AllowanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw721::{ApprovalResponse, OperatorsResponse, OwnerOfResponse, TokensResponse};
 use cw721_base::QueryMsg as Cw721QueryMsg;
 use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension};
 
-use crate::contract::DENOM;
 use crate::error::ContractError;
-use crate::state::{CW721Swap, SwapType};
+use crate::state::{
+    has_role, CW721Swap, Config, Role, BLOCKED_ADDRESSES, CONFIG, PAUSED, PAYMENT_TOKENS,
+};
 
 // Default and Max page sizes for paginated queries
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 10;
 
+// Fees are expressed in basis points (10000 = 100%)
+pub const FEE_BPS_DENOMINATOR: u64 = 10_000;
+// Highest marketplace fee that can be set (10%)
+pub const MAX_FEE_BPS: u64 = 1_000;
+
+// Longest update delay, in blocks (about a week at 6 second blocks)
+pub const MAX_UPDATE_DELAY: u64 = 100_000;
+
+// Most listings looked at, cheapest first, when working out a floor price
+pub const MAX_FLOOR_SCAN: usize = 50;
+
 // Pagination query pagaination parameters for filtered swap queries
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PageParams {
@@ -28,6 +40,42 @@ pub struct PageParams {
 }
 
 // Read utils
+pub fn check_admin(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    if config.admin.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+// Admin, or a member of `role`
+pub fn check_role(
+    storage: &dyn Storage,
+    config: &Config,
+    role: &Role,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if config.admin.as_ref() != Some(sender) && !has_role(storage, role, sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn check_not_blocked(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    if BLOCKED_ADDRESSES.has(storage, address) {
+        return Err(ContractError::AddressBlocked {
+            address: address.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn check_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    if PAUSED.may_load(storage)?.unwrap_or(false) {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
 pub fn query_name_owner(
     id: &str,
     cw721: &Addr,
@@ -39,12 +87,122 @@ pub fn query_name_owner(
     };
     let req = QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: cw721.to_string(),
-        msg: to_binary(&query_msg).unwrap(),
+        msg: to_binary(&query_msg)?,
     });
     let res: OwnerOfResponse = deps.querier.query(&req)?;
     Ok(res)
 }
 
+// Token ids of the collection held by `owner`
+pub fn query_owned_tokens(
+    deps: Deps,
+    cw721: &Addr,
+    owner: &Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let res: TokensResponse = deps.querier.query_wasm_smart(
+        cw721,
+        &Cw721QueryMsg::Tokens {
+            owner: owner.to_string(),
+            start_after,
+            limit,
+        },
+    )?;
+    Ok(res.tokens)
+}
+
+// Checks the marketplace can transfer `token_id` on behalf of `owner`,
+// either through a token approval or an operator approval
+pub fn check_nft_approval(
+    deps: &DepsMut,
+    env: &Env,
+    cw721: &Addr,
+    token_id: &str,
+    owner: &Addr,
+) -> Result<(), ContractError> {
+    let spender = env.contract.address.to_string();
+    let approval: StdResult<ApprovalResponse> = deps.querier.query_wasm_smart(
+        cw721,
+        &Cw721QueryMsg::Approval {
+            token_id: token_id.to_owned(),
+            spender: spender.clone(),
+            include_expired: None,
+        },
+    );
+    if approval.is_ok() {
+        return Ok(());
+    }
+
+    let operators: OperatorsResponse = deps.querier.query_wasm_smart(
+        cw721,
+        &Cw721QueryMsg::AllOperators {
+            owner: owner.to_string(),
+            include_expired: None,
+            start_after: None,
+            limit: Some(MAX_LIMIT),
+        },
+    )?;
+    if operators.operators.iter().any(|op| op.spender == spender) {
+        return Ok(());
+    }
+
+    Err(ContractError::MissingApproval {
+        token_id: token_id.to_owned(),
+    })
+}
+
+// Checks `owner` has allowed the marketplace to spend
+// at least `required` of the cw20 `token`
+pub fn check_cw20_allowance(
+    deps: &DepsMut,
+    env: &Env,
+    token: &Addr,
+    owner: &Addr,
+    required: Uint128,
+) -> Result<(), ContractError> {
+    let res: AllowanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Allowance {
+            owner: owner.to_string(),
+            spender: env.contract.address.to_string(),
+        },
+    )?;
+    let allowance = if res.expires.is_expired(&env.block) {
+        Uint128::zero()
+    } else {
+        res.allowance
+    };
+    if allowance < required {
+        return Err(ContractError::InsufficientAllowance {
+            token: token.to_string(),
+            expected: required,
+            allowance,
+        });
+    }
+    Ok(())
+}
+
+// Checks a cw20 is allowlisted for payment
+// and `price` meets its minimum price (if any)
+pub fn check_payment_token(
+    deps: &DepsMut,
+    payment_token: &Addr,
+    price: Uint128,
+) -> Result<(), ContractError> {
+    let token = PAYMENT_TOKENS
+        .may_load(deps.storage, payment_token)?
+        .ok_or_else(|| ContractError::PaymentTokenNotAllowed {
+            token: payment_token.to_string(),
+        })?;
+    if let Some(min_price) = token.min_price {
+        if price < min_price {
+            return Err(ContractError::PriceTooLow { price, min_price });
+        }
+    }
+    Ok(())
+}
+
 pub fn calculate_page_params(
     page: Option<u32>,
     limit: Option<u32>,
@@ -55,10 +213,8 @@ pub fn calculate_page_params(
     // Calculate dynamic limit and last page size
     if total_results < limit {
         limit = total_results;
-    } else if limit < DEFAULT_LIMIT {
-        limit = DEFAULT_LIMIT;
-    } else if limit > MAX_LIMIT {
-        limit = MAX_LIMIT;
+    } else {
+        limit = limit.clamp(DEFAULT_LIMIT, MAX_LIMIT);
     }
     let modulo = if total_results > 0 {
         total_results % limit
@@ -101,17 +257,31 @@ pub fn check_sent_required_payment(
     if let Some(required_coin) = required {
         let required_amount = required_coin.amount.u128();
         if required_amount > 0 {
-            let sent_sufficient_funds = sent.iter().any(|coin| {
-                // check if a given sent coin matches denom
-                // and has sufficient amount
-                coin.denom == required_coin.denom && coin.amount.u128() >= required_amount
-            });
-
-            if sent_sufficient_funds {
-                return Ok(());
-            } else {
-                return Err(ContractError::Unauthorized {});
-            }
+            // check if a sent coin matches denom
+            // and has sufficient amount
+            let sent_coin = sent.iter().find(|coin| coin.denom == required_coin.denom);
+            return match sent_coin {
+                Some(coin) if coin.amount.u128() >= required_amount => Ok(()),
+                Some(coin) => Err(ContractError::InsufficientFunds {
+                    expected: required_coin,
+                    sent: coin.clone(),
+                }),
+                None if !sent.is_empty() => Err(ContractError::WrongDenom {
+                    expected: required_coin.denom,
+                    sent: sent
+                        .iter()
+                        .map(|coin| coin.denom.clone())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                }),
+                None => Err(ContractError::InsufficientFunds {
+                    sent: Coin {
+                        denom: required_coin.denom.clone(),
+                        amount: Uint128::zero(),
+                    },
+                    expected: required_coin,
+                }),
+            };
         }
     }
     Ok(())
@@ -145,9 +315,12 @@ pub fn check_contract_balance_ok(
     deps: &DepsMut,
     required: Coin,
 ) -> Result<(), ContractError> {
-    let required_denom: String = DENOM.to_string();
+    let required_denom: String = CONFIG.load(deps.storage)?.denom;
     if required.denom != required_denom {
-        return Err(ContractError::InsufficientBalance {});
+        return Err(ContractError::WrongDenom {
+            expected: required_denom,
+            sent: required.denom,
+        });
     }
     let swap_instance: &Addr = &env.contract.address;
     let required_amount = required.amount.u128();
@@ -171,47 +344,114 @@ pub fn check_contract_balance_ok(
     Ok(())
 }
 
+// Returns the sent funds left over once `payment` is deducted,
+// e.g. overpayments and any denoms not required by the swap
+pub fn calculate_refund(sent: &[Coin], payment: Option<&Coin>) -> Vec<Coin> {
+    sent.iter()
+        .filter_map(|coin| {
+            let amount = match payment {
+                Some(required) if required.denom == coin.denom => {
+                    coin.amount.saturating_sub(required.amount)
+                }
+                _ => coin.amount,
+            };
+            if amount.is_zero() {
+                None
+            } else {
+                Some(Coin {
+                    denom: coin.denom.clone(),
+                    amount,
+                })
+            }
+        })
+        .collect()
+}
+
+// Marketplace fee retained from a swap of `price`,
+// no fees are retained unless a treasury is set
+pub fn calculate_fee(config: &Config, price: Uint128) -> Uint128 {
+    if config.treasury.is_none() {
+        return Uint128::zero();
+    }
+    price.multiply_ratio(config.fee_bps, FEE_BPS_DENOMINATOR)
+}
+
+// Length of a name (e.g. `alice.arch`) in characters, excluding its TLD
+pub fn name_length(token_id: &str) -> u32 {
+    let name = token_id.rsplit_once('.').map_or(token_id, |(name, _)| name);
+    name.chars().count() as u32
+}
+
+// TLD of a name (e.g. `arch` for `alice.arch`)
+pub fn name_tld(token_id: &str) -> Option<&str> {
+    token_id.rsplit_once('.').map(|(_, tld)| tld)
+}
+
+// Native denoms must be 3 - 128 characters of `[a-zA-Z0-9/:._-]`
+// (e.g. `aarch`, `ibc/...` or `factory/...` denoms)
+pub fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let valid_chars = denom
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | ':' | '.' | '_' | '-'));
+    if denom.len() < 3 || denom.len() > 128 || !valid_chars {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
 // Write utils
 pub fn handle_swap_transfers(
     nft_sender: &Addr,
     nft_receiver: &Addr,
     details: CW721Swap,
-    funds: &[Coin],
+    config: &Config,
 ) -> StdResult<Vec<CosmosMsg>> {
-    // cw20 swap
-    let payment_callback: CosmosMsg = if details.payment_token.is_some() {
-        let token_transfer_msg = Cw20ExecuteMsg::TransferFrom {
-            owner: nft_receiver.to_string(),
-            recipient: nft_sender.to_string(),
-            amount: details.price,
-        };
+    // Seller receives exactly the swap price minus fees,
+    // any overpayment is refunded to the buyer
+    let fee = calculate_fee(config, details.price);
+    let mut payments: Vec<(String, Uint128)> =
+        vec![(nft_sender.to_string(), details.price.checked_sub(fee)?)];
+    if let Some(treasury) = &config.treasury {
+        payments.push((treasury.to_string(), fee));
+    }
 
-        let cw20_callback: CosmosMsg = WasmMsg::Execute {
-            contract_addr: details.payment_token.unwrap().into(),
-            msg: to_binary(&token_transfer_msg)?,
-            funds: vec![],
+    let mut payment_callbacks: Vec<CosmosMsg> = vec![];
+    for (recipient, amount) in payments.into_iter() {
+        if amount.is_zero() {
+            continue;
         }
-        .into();
-        cw20_callback
-    // aarch swap
-    } else {
-        let payment_funds = if details.swap_type == SwapType::Sale {
-            funds.to_vec()
+        // cw20 swap
+        let payment_callback: CosmosMsg = if let Some(payment_token) = &details.payment_token {
+            let token_transfer_msg = Cw20ExecuteMsg::TransferFrom {
+                owner: nft_receiver.to_string(),
+                recipient,
+                amount,
+            };
+
+            let cw20_callback: CosmosMsg = WasmMsg::Execute {
+                contract_addr: payment_token.to_string(),
+                msg: to_binary(&token_transfer_msg)?,
+                funds: vec![],
+            }
+            .into();
+            cw20_callback
+        // native swap
         } else {
-            ([Coin {
-                denom: String::from(DENOM),
-                amount: details.price,
-            }])
-            .to_vec()
-        };
-        let aarch_transfer_msg = BankMsg::Send {
-            to_address: nft_sender.to_string(),
-            amount: payment_funds,
-        };
+            let native_transfer_msg = BankMsg::Send {
+                to_address: recipient,
+                amount: vec![Coin {
+                    denom: details.native_denom(&config.denom),
+                    amount,
+                }],
+            };
 
-        let aarch_callback: CosmosMsg = cosmwasm_std::CosmosMsg::Bank(aarch_transfer_msg);
-        aarch_callback
-    };
+            let native_callback: CosmosMsg = cosmwasm_std::CosmosMsg::Bank(native_transfer_msg);
+            native_callback
+        };
+        payment_callbacks.push(payment_callback);
+    }
 
     let nft_transfer_msg = Cw721ExecuteMsg::<Extension>::TransferNft {
         recipient: nft_receiver.to_string(),
@@ -225,5 +465,7 @@ pub fn handle_swap_transfers(
     }
     .into();
 
-    Ok(vec![cw721_callback, payment_callback])
+    let mut callbacks = vec![cw721_callback];
+    callbacks.append(&mut payment_callbacks);
+    Ok(callbacks)
 }